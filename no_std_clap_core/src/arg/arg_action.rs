@@ -0,0 +1,24 @@
+// What happens when an argument is encountered on the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgAction {
+    // Flag, stores `true` (`--flag=false` is accepted)
+    SetTrue,
+    // Flag, stores `false` (`--flag=true` is accepted)
+    SetFalse,
+    // Takes a value
+    Set,
+    // Takes a value, every occurrence is kept
+    Append,
+    // Counts the occurrences, e.g. `-vvv`
+    Count,
+    // Requests the help message
+    Help,
+    // Requests the version
+    Version,
+}
+
+impl ArgAction {
+    pub fn takes_value(&self) -> bool {
+        matches!(self, ArgAction::Set | ArgAction::Append)
+    }
+}
@@ -0,0 +1,44 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+// Set of arguments validated together, e.g. "exactly one of --file, --url, --stdin"
+#[derive(Debug, Clone)]
+pub struct ArgGroup {
+    pub name: String,
+    pub args: Vec<String>,
+    // More than one argument of the group can be used at once
+    pub multiple: bool,
+    // At least one argument of the group must be used
+    pub required: bool,
+}
+
+impl ArgGroup {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            args: Vec::new(),
+            multiple: false,
+            required: false,
+        }
+    }
+
+    pub fn arg(mut self, arg: &str) -> Self {
+        self.args.push(arg.to_string());
+        self
+    }
+
+    pub fn args(mut self, args: &[&str]) -> Self {
+        self.args.extend(args.iter().map(|arg| arg.to_string()));
+        self
+    }
+
+    pub fn multiple(mut self) -> Self {
+        self.multiple = true;
+        self
+    }
+
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+}
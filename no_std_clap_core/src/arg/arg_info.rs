@@ -1,4 +1,9 @@
+use alloc::format;
 use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use crate::arg::arg_action::ArgAction;
+use crate::arg::arg_range::ArgRange;
+use crate::arg::value_range::ValueRange;
 
 // Argument metadata
 #[derive(Debug, Clone)]
@@ -7,11 +12,34 @@ pub struct ArgInfo {
     pub short: Option<char>,
     pub long: Option<String>,
     pub help: Option<String>,
+    pub action: ArgAction,
+    pub num_args: Option<ValueRange>,
+    // Allowed numeric values
+    pub range: Option<ArgRange>,
+    // Only these values are accepted when not empty
+    pub possible_values: Vec<String>,
     pub required: bool,
     pub multiple: bool,
     pub global: bool,
     pub count: bool,
-    pub used: bool
+    pub last: bool,
+    pub allow_hyphen_values: bool,
+    // 1-based position among the positional arguments
+    pub index: Option<usize>,
+    // Names of the arguments that cannot be used together with this one
+    pub conflicts_with: Vec<String>,
+    // Names of the arguments that must be present when this one is
+    pub requires: Vec<String>,
+    // Names of the arguments whose values are discarded when this one is used
+    pub overrides_with: Vec<String>,
+    // Required when the given argument has the given value
+    pub required_if_eq: Vec<(String, String)>,
+    // Required unless one of these arguments is present
+    pub required_unless_present: Vec<String>,
+    // Argument, value and default to use when the argument has that value
+    pub default_value_if: Vec<(String, String, String)>,
+    // A flag also accepting `--no-<long>` to set it to false
+    pub negatable: bool,
 }
 
 impl ArgInfo {
@@ -21,11 +49,24 @@ impl ArgInfo {
             short: None,
             long: None,
             help: None,
+            action: ArgAction::Set,
+            num_args: None,
+            range: None,
+            possible_values: Vec::new(),
             required: false,
             multiple: false,
             global: false,
             count: false,
-            used: false,
+            last: false,
+            allow_hyphen_values: false,
+            index: None,
+            conflicts_with: Vec::new(),
+            requires: Vec::new(),
+            overrides_with: Vec::new(),
+            required_if_eq: Vec::new(),
+            required_unless_present: Vec::new(),
+            default_value_if: Vec::new(),
+            negatable: false,
         }
     }
 
@@ -44,6 +85,32 @@ impl ArgInfo {
         self
     }
 
+    pub fn action(mut self, action: ArgAction) -> Self {
+        self.action = action;
+        self.count = action == ArgAction::Count;
+        if action == ArgAction::Append {
+            self.multiple = true;
+        }
+        self
+    }
+
+    // Values taken per occurrence, e.g. `2..=3` for `--point 1 2 3`
+    pub fn num_args(mut self, num_args: impl Into<ValueRange>) -> Self {
+        self.num_args = Some(num_args.into());
+        self
+    }
+
+    // Numeric values must be in this range, e.g. `1..=14`
+    pub fn range(mut self, range: impl Into<ArgRange>) -> Self {
+        self.range = Some(range.into());
+        self
+    }
+
+    pub fn possible_values(mut self, values: &[&str]) -> Self {
+        self.possible_values = values.iter().map(|value| value.to_string()).collect();
+        self
+    }
+
     pub fn required(mut self) -> Self {
         self.required = true;
         self
@@ -51,6 +118,9 @@ impl ArgInfo {
 
     pub fn multiple(mut self) -> Self {
         self.multiple = true;
+        if self.action == ArgAction::Set {
+            self.action = ArgAction::Append;
+        }
         self
     }
 
@@ -61,6 +131,79 @@ impl ArgInfo {
 
     pub fn count(mut self) -> Self {
         self.count = true;
+        self.action = ArgAction::Count;
+        self
+    }
+
+    // 1-based position among the positionals, the ones without an index come after
+    pub fn index(mut self, index: usize) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    pub fn conflicts_with(mut self, name: &str) -> Self {
+        self.conflicts_with.push(name.to_string());
+        self
+    }
+
+    pub fn requires(mut self, name: &str) -> Self {
+        self.requires.push(name.to_string());
+        self
+    }
+
+    pub fn overrides_with(mut self, name: &str) -> Self {
+        self.overrides_with.push(name.to_string());
         self
     }
+
+    pub fn negatable(mut self) -> Self {
+        self.negatable = true;
+        self
+    }
+
+    pub fn required_if_eq(mut self, name: &str, value: &str) -> Self {
+        self.required_if_eq.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn required_unless_present(mut self, name: &str) -> Self {
+        self.required_unless_present.push(name.to_string());
+        self
+    }
+
+    // Use `default` when this argument is missing and `name` has `value`, e.g. `default_value_if("mode", "fast", "8")`
+    pub fn default_value_if(mut self, name: &str, value: &str, default: &str) -> Self {
+        self.default_value_if.push((name.to_string(), value.to_string(), default.to_string()));
+        self
+    }
+
+    // Positional argument only filled by the values following `--`
+    pub fn last(mut self) -> Self {
+        self.last = true;
+        self
+    }
+
+    // Accept values starting with '-', e.g. `--pattern -foo`
+    pub fn allow_hyphen_values(mut self) -> Self {
+        self.allow_hyphen_values = true;
+        self
+    }
+
+    // Values taken per occurrence when the action takes a value
+    pub fn value_range(&self) -> ValueRange {
+        self.num_args.unwrap_or(ValueRange::SINGLE)
+    }
+
+    // Name of the argument as typed by the user, e.g. `--name` or `<FILE>`
+    pub fn display_name(&self) -> String {
+        if let Some(long) = &self.long {
+            format!("--{}", long)
+        }
+        else if let Some(short) = self.short {
+            format!("-{}", short)
+        }
+        else {
+            format!("<{}>", self.name.to_uppercase())
+        }
+    }
 }
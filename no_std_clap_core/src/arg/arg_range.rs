@@ -0,0 +1,190 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::fmt::Display;
+use core::ops::{Bound, Range, RangeFrom, RangeInclusive, RangeTo, RangeToInclusive};
+
+// Allowed numeric values of an argument, e.g. `1..=14`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArgRange {
+    pub start: Bound<RangeBound>,
+    pub end: Bound<RangeBound>,
+}
+
+// Integer bounds are compared as integers, so that `u64` or `i64` bounds keep their precision
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RangeBound {
+    Integer(i128),
+    Float(f64),
+}
+
+impl RangeBound {
+    // Order of `value` compared to the bound, none when it is not a number. A float value is compared to an
+    // integer bound as a float
+    fn compare(&self, value: &str) -> Option<Ordering> {
+        match self {
+            RangeBound::Integer(bound) => match value.parse::<i128>() {
+                Ok(value) => Some(value.cmp(bound)),
+                Err(_) => value.parse::<f64>().ok().and_then(|value| value.partial_cmp(&(*bound as f64))),
+            },
+            RangeBound::Float(bound) => value.parse::<f64>().ok().and_then(|value| value.partial_cmp(bound)),
+        }
+    }
+}
+
+// Numbers usable as bounds of an `ArgRange`
+pub trait IntoRangeBound {
+    fn into_range_bound(self) -> RangeBound;
+}
+
+impl IntoRangeBound for i8 {
+    fn into_range_bound(self) -> RangeBound {
+        RangeBound::Integer(self.into())
+    }
+}
+
+impl IntoRangeBound for i16 {
+    fn into_range_bound(self) -> RangeBound {
+        RangeBound::Integer(self.into())
+    }
+}
+
+impl IntoRangeBound for i32 {
+    fn into_range_bound(self) -> RangeBound {
+        RangeBound::Integer(self.into())
+    }
+}
+
+impl IntoRangeBound for i64 {
+    fn into_range_bound(self) -> RangeBound {
+        RangeBound::Integer(self.into())
+    }
+}
+
+impl IntoRangeBound for i128 {
+    fn into_range_bound(self) -> RangeBound {
+        RangeBound::Integer(self)
+    }
+}
+
+impl IntoRangeBound for isize {
+    fn into_range_bound(self) -> RangeBound {
+        RangeBound::Integer(self as i128)
+    }
+}
+
+impl IntoRangeBound for u8 {
+    fn into_range_bound(self) -> RangeBound {
+        RangeBound::Integer(self.into())
+    }
+}
+
+impl IntoRangeBound for u16 {
+    fn into_range_bound(self) -> RangeBound {
+        RangeBound::Integer(self.into())
+    }
+}
+
+impl IntoRangeBound for u32 {
+    fn into_range_bound(self) -> RangeBound {
+        RangeBound::Integer(self.into())
+    }
+}
+
+impl IntoRangeBound for u64 {
+    fn into_range_bound(self) -> RangeBound {
+        RangeBound::Integer(self.into())
+    }
+}
+
+impl IntoRangeBound for usize {
+    fn into_range_bound(self) -> RangeBound {
+        RangeBound::Integer(self as i128)
+    }
+}
+
+impl IntoRangeBound for f32 {
+    fn into_range_bound(self) -> RangeBound {
+        RangeBound::Float(self.into())
+    }
+}
+
+impl IntoRangeBound for f64 {
+    fn into_range_bound(self) -> RangeBound {
+        RangeBound::Float(self)
+    }
+}
+
+impl ArgRange {
+    // Values which are not a number are outside of the range
+    pub fn contains(&self, value: &str) -> bool {
+        let after_start = match self.start {
+            Bound::Included(start) => start.compare(value).is_some_and(Ordering::is_ge),
+            Bound::Excluded(start) => start.compare(value).is_some_and(Ordering::is_gt),
+            Bound::Unbounded => true,
+        };
+
+        let before_end = match self.end {
+            Bound::Included(end) => end.compare(value).is_some_and(Ordering::is_le),
+            Bound::Excluded(end) => end.compare(value).is_some_and(Ordering::is_lt),
+            Bound::Unbounded => true,
+        };
+
+        after_start && before_end
+    }
+}
+
+impl<T: IntoRangeBound> From<Range<T>> for ArgRange {
+    fn from(range: Range<T>) -> Self {
+        Self { start: Bound::Included(range.start.into_range_bound()), end: Bound::Excluded(range.end.into_range_bound()) }
+    }
+}
+
+impl<T: IntoRangeBound> From<RangeInclusive<T>> for ArgRange {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let (start, end) = range.into_inner();
+        Self { start: Bound::Included(start.into_range_bound()), end: Bound::Included(end.into_range_bound()) }
+    }
+}
+
+impl<T: IntoRangeBound> From<RangeFrom<T>> for ArgRange {
+    fn from(range: RangeFrom<T>) -> Self {
+        Self { start: Bound::Included(range.start.into_range_bound()), end: Bound::Unbounded }
+    }
+}
+
+impl<T: IntoRangeBound> From<RangeTo<T>> for ArgRange {
+    fn from(range: RangeTo<T>) -> Self {
+        Self { start: Bound::Unbounded, end: Bound::Excluded(range.end.into_range_bound()) }
+    }
+}
+
+impl<T: IntoRangeBound> From<RangeToInclusive<T>> for ArgRange {
+    fn from(range: RangeToInclusive<T>) -> Self {
+        Self { start: Bound::Unbounded, end: Bound::Included(range.end.into_range_bound()) }
+    }
+}
+
+impl Display for RangeBound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RangeBound::Integer(bound) => write!(f, "{}", bound),
+            RangeBound::Float(bound) => write!(f, "{}", bound),
+        }
+    }
+}
+
+// Written the way it is declared, e.g. `1..=14` or `0.5..`
+impl Display for ArgRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.start {
+            Bound::Included(start) | Bound::Excluded(start) => write!(f, "{}..", start)?,
+            Bound::Unbounded => write!(f, "..")?,
+        }
+
+        match self.end {
+            Bound::Included(end) => write!(f, "={}", end),
+            Bound::Excluded(end) => write!(f, "{}", end),
+            Bound::Unbounded => Ok(()),
+        }
+    }
+}
@@ -1,11 +1,43 @@
-use alloc::format;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
-use crate::error::ParseError;
+use core::error::Error;
+use crate::arg::arg_info::ArgInfo;
+use crate::error::{ErrorKind, ParseError};
 
 // Trait for types that can be parsed from command line arguments
 pub trait FromArg: Sized {
     fn from_arg(arg: &str) -> Result<Self, ParseError>;
+
+    // Conversion knowing the argument the value was given to, used by the derives
+    fn from_arg_with(arg: &str, _info: &ArgInfo) -> Result<Self, ParseError> {
+        Self::from_arg(arg)
+    }
+}
+
+// Conversion with an error type of its own, given the argument the value was given to (its name, range, ...),
+// `None` when converted without a command by `FromArg::from_arg`. Every `TryFromArg` type is a `FromArg`, its
+// error is kept as the `source` of the `ParseError`
+pub trait TryFromArg: Sized {
+    type Error: Error + Send + Sync + 'static;
+
+    fn try_from_arg(arg: &str, info: Option<&ArgInfo>) -> Result<Self, Self::Error>;
+}
+
+impl<T: TryFromArg> FromArg for T {
+    fn from_arg(arg: &str) -> Result<Self, ParseError> {
+        T::try_from_arg(arg, None).map_err(|error| ParseError::new(ErrorKind::InvalidValue)
+            .value(arg)
+            .message(error.to_string())
+            .with_source(error))
+    }
+
+    fn from_arg_with(arg: &str, info: &ArgInfo) -> Result<Self, ParseError> {
+        T::try_from_arg(arg, Some(info)).map_err(|error| ParseError::new(ErrorKind::InvalidArgumentValue)
+            .arg(info.display_name())
+            .value(arg)
+            .message(error.to_string())
+            .with_source(error))
+    }
 }
 
 // Implement FromArg for primitive types
@@ -17,73 +49,73 @@ impl FromArg for String {
 
 impl FromArg for i8 {
     fn from_arg(arg: &str) -> Result<Self, ParseError> {
-        arg.parse().map_err(|_| ParseError::InvalidValue(format!("Cannot parse '{}' as i8", arg)))
+        arg.parse().map_err(|error| ParseError::new(ErrorKind::InvalidValue).value(arg).value_type("i8").with_source(error))
     }
 }
 
 impl FromArg for i16 {
     fn from_arg(arg: &str) -> Result<Self, ParseError> {
-        arg.parse().map_err(|_| ParseError::InvalidValue(format!("Cannot parse '{}' as i16", arg)))
+        arg.parse().map_err(|error| ParseError::new(ErrorKind::InvalidValue).value(arg).value_type("i16").with_source(error))
     }
 }
 
 impl FromArg for i32 {
     fn from_arg(arg: &str) -> Result<Self, ParseError> {
-        arg.parse().map_err(|_| ParseError::InvalidValue(format!("Cannot parse '{}' as i32", arg)))
+        arg.parse().map_err(|error| ParseError::new(ErrorKind::InvalidValue).value(arg).value_type("i32").with_source(error))
     }
 }
 
 impl FromArg for i64 {
     fn from_arg(arg: &str) -> Result<Self, ParseError> {
-        arg.parse().map_err(|_| ParseError::InvalidValue(format!("Cannot parse '{}' as i64", arg)))
+        arg.parse().map_err(|error| ParseError::new(ErrorKind::InvalidValue).value(arg).value_type("i64").with_source(error))
     }
 }
 
 impl FromArg for isize {
     fn from_arg(arg: &str) -> Result<Self, ParseError> {
-        arg.parse().map_err(|_| ParseError::InvalidValue(format!("Cannot parse '{}' as isize", arg)))
+        arg.parse().map_err(|error| ParseError::new(ErrorKind::InvalidValue).value(arg).value_type("isize").with_source(error))
     }
 }
 
 impl FromArg for u8 {
     fn from_arg(arg: &str) -> Result<Self, ParseError> {
-        arg.parse().map_err(|_| ParseError::InvalidValue(format!("Cannot parse '{}' as u8", arg)))
+        arg.parse().map_err(|error| ParseError::new(ErrorKind::InvalidValue).value(arg).value_type("u8").with_source(error))
     }
 }
 
 impl FromArg for u16 {
     fn from_arg(arg: &str) -> Result<Self, ParseError> {
-        arg.parse().map_err(|_| ParseError::InvalidValue(format!("Cannot parse '{}' as u16", arg)))
+        arg.parse().map_err(|error| ParseError::new(ErrorKind::InvalidValue).value(arg).value_type("u16").with_source(error))
     }
 }
 
 impl FromArg for u32 {
     fn from_arg(arg: &str) -> Result<Self, ParseError> {
-        arg.parse().map_err(|_| ParseError::InvalidValue(format!("Cannot parse '{}' as u32", arg)))
+        arg.parse().map_err(|error| ParseError::new(ErrorKind::InvalidValue).value(arg).value_type("u32").with_source(error))
     }
 }
 
 impl FromArg for u64 {
     fn from_arg(arg: &str) -> Result<Self, ParseError> {
-        arg.parse().map_err(|_| ParseError::InvalidValue(format!("Cannot parse '{}' as u64", arg)))
+        arg.parse().map_err(|error| ParseError::new(ErrorKind::InvalidValue).value(arg).value_type("u64").with_source(error))
     }
 }
 
 impl FromArg for usize {
     fn from_arg(arg: &str) -> Result<Self, ParseError> {
-        arg.parse().map_err(|_| ParseError::InvalidValue(format!("Cannot parse '{}' as usize", arg)))
+        arg.parse().map_err(|error| ParseError::new(ErrorKind::InvalidValue).value(arg).value_type("usize").with_source(error))
     }
 }
 
 impl FromArg for f32 {
     fn from_arg(arg: &str) -> Result<Self, ParseError> {
-        arg.parse().map_err(|_| ParseError::InvalidValue(format!("Cannot parse '{}' as f32", arg)))
+        arg.parse().map_err(|error| ParseError::new(ErrorKind::InvalidValue).value(arg).value_type("f32").with_source(error))
     }
 }
 
 impl FromArg for f64 {
     fn from_arg(arg: &str) -> Result<Self, ParseError> {
-        arg.parse().map_err(|_| ParseError::InvalidValue(format!("Cannot parse '{}' as f64", arg)))
+        arg.parse().map_err(|error| ParseError::new(ErrorKind::InvalidValue).value(arg).value_type("f64").with_source(error))
     }
 }
 
@@ -92,7 +124,7 @@ impl FromArg for bool {
         match arg.to_lowercase().as_str() {
             "true" | "1" | "yes" | "on" => Ok(true),
             "false" | "0" | "no" | "off" => Ok(false),
-            _ => Err(ParseError::InvalidValue(format!("Cannot parse '{}' as bool", arg))),
+            _ => Err(ParseError::new(ErrorKind::InvalidValue).value(arg).value_type("bool")),
         }
     }
 }
@@ -102,6 +134,10 @@ impl<T: FromArg> FromArg for Option<T> {
     fn from_arg(arg: &str) -> Result<Self, ParseError> {
         Ok(Some(T::from_arg(arg)?))
     }
+
+    fn from_arg_with(arg: &str, info: &ArgInfo) -> Result<Self, ParseError> {
+        Ok(Some(T::from_arg_with(arg, info)?))
+    }
 }
 
 // Vec types for multiple values
@@ -112,4 +148,11 @@ impl<T: FromArg> FromArg for Vec<T> {
             .map(|s| T::from_arg(s.trim()))
             .collect()
     }
+
+    fn from_arg_with(arg: &str, info: &ArgInfo) -> Result<Self, ParseError> {
+        arg
+            .split(',')
+            .map(|s| T::from_arg_with(s.trim(), info))
+            .collect()
+    }
 }
\ No newline at end of file
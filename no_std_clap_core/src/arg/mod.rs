@@ -1,3 +1,7 @@
+pub mod arg_action;
+pub mod arg_group;
 pub mod arg_info;
+pub mod arg_range;
 pub mod from_arg;
-pub mod parsed_arg;
\ No newline at end of file
+pub mod parsed_arg;
+pub mod value_range;
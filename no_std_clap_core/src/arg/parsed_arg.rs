@@ -6,24 +6,47 @@ use alloc::vec::Vec;
 pub struct ParsedArgs {
     pub args: BTreeMap<String, Vec<String>>,
     pub counts: BTreeMap<String, usize>,
+    // Number of values given by each occurrence of an argument
+    pub occurrences: BTreeMap<String, Vec<usize>>,
     pub subcommand: Option<(String, Box<ParsedArgs>)>,
 }
 
+impl Default for ParsedArgs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ParsedArgs {
     pub fn new() -> Self {
         Self {
             args: BTreeMap::new(),
             counts: BTreeMap::new(),
+            occurrences: BTreeMap::new(),
             subcommand: None,
         }
     }
 
     pub fn insert(&mut self, key: String, value: String) {
-        self.args.entry(key).or_insert_with(Vec::new).push(value);
+        self.occurrences.entry(key.clone()).or_default().push(1);
+        self.args.entry(key).or_default().push(value);
+    }
+
+    // Insert all the values given by one occurrence
+    pub fn insert_occurrence(&mut self, key: String, values: Vec<String>) {
+        self.occurrences.entry(key.clone()).or_default().push(values.len());
+        self.args.entry(key).or_default().extend(values);
+    }
+
+    // Forget every value of an argument, e.g. when it is overridden
+    pub fn remove(&mut self, key: &str) {
+        self.args.remove(key);
+        self.occurrences.remove(key);
+        self.counts.remove(key);
     }
 
     pub fn insert_flag(&mut self, key: String) {
-        self.args.entry(key).or_insert_with(Vec::new).push(String::new());
+        self.insert(key, String::new());
     }
 
     pub fn increment(&mut self, name: String) {
@@ -32,7 +55,7 @@ impl ParsedArgs {
     }
 
     pub fn count(&self, key: &str) -> usize {
-        self.counts.get(key).map(|v| *v).unwrap_or(0usize)
+        self.counts.get(key).copied().unwrap_or(0usize)
     }
 
     pub fn set_subcommand(&mut self, name: String, args: ParsedArgs) {
@@ -52,7 +75,35 @@ impl ParsedArgs {
     pub fn get_all(&self, key: &str) -> Vec<&str> {
         self.args.get(key)
             .map(|values| values.iter().map(|s| s.as_str()).collect())
-            .unwrap_or_else(Vec::new)
+            .unwrap_or_default()
+    }
+
+    // Get the values grouped by occurrence, e.g. `--point 1 2 --point 3 4` gives [[1, 2], [3, 4]]
+    pub fn get_occurrences(&self, key: &str) -> Vec<Vec<&str>> {
+        let (Some(values), Some(occurrences)) = (self.args.get(key), self.occurrences.get(key))
+        else {
+            return Vec::new();
+        };
+
+        let mut start = 0;
+        occurrences
+            .iter()
+            .map(|count| {
+                let group = values[start..start + count].iter().map(|s| s.as_str()).collect();
+                start += count;
+                group
+            })
+            .collect()
+    }
+
+    // Get the state of a SetTrue/SetFalse flag, None if it was not provided
+    pub fn get_flag(&self, key: &str) -> Option<bool> {
+        self.args.get(key)?.last().map(|value| value == "true")
+    }
+
+    // Check if an argument was provided, counted ones included
+    pub fn is_present(&self, key: &str) -> bool {
+        self.args.contains_key(key) || self.count(key) > 0
     }
 
     // Check if an argument was provided (for boolean flags)
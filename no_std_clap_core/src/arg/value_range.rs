@@ -0,0 +1,44 @@
+use core::ops::{Range, RangeFrom, RangeInclusive};
+
+// Number of values an argument takes per occurrence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValueRange {
+    pub min: usize,
+    pub max: usize,
+}
+
+impl ValueRange {
+    pub const SINGLE: ValueRange = ValueRange { min: 1, max: 1 };
+
+    pub fn new(min: usize, max: usize) -> Self {
+        Self { min, max }
+    }
+
+    pub fn takes_multiple(&self) -> bool {
+        self.max > 1
+    }
+}
+
+impl From<usize> for ValueRange {
+    fn from(count: usize) -> Self {
+        Self::new(count, count)
+    }
+}
+
+impl From<Range<usize>> for ValueRange {
+    fn from(range: Range<usize>) -> Self {
+        Self::new(range.start, range.end.saturating_sub(1))
+    }
+}
+
+impl From<RangeInclusive<usize>> for ValueRange {
+    fn from(range: RangeInclusive<usize>) -> Self {
+        Self::new(*range.start(), *range.end())
+    }
+}
+
+impl From<RangeFrom<usize>> for ValueRange {
+    fn from(range: RangeFrom<usize>) -> Self {
+        Self::new(range.start, usize::MAX)
+    }
+}
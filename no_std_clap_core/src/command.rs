@@ -1,11 +1,30 @@
+use crate::arg::arg_action::ArgAction;
+use crate::arg::arg_group::ArgGroup;
 use crate::arg::arg_info::ArgInfo;
+use crate::arg::from_arg::FromArg;
 use crate::arg::parsed_arg::ParsedArgs;
-use crate::error::ParseError;
-use crate::help::get_help;
+use crate::error::{DefinitionError, ErrorKind, ParseError, ParseErrors};
+use crate::help::{get_help, get_usage};
+use crate::messages::{English, Messages};
+use crate::parser::ParseContext;
 use crate::subcommand::SubcommandInfo;
+use crate::suggestion::did_you_mean;
+use crate::validate::validate_level;
+use alloc::format;
 use alloc::string::{String, ToString};
+use alloc::vec;
 use alloc::vec::Vec;
 use core::fmt::Write;
+use core::mem;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+// Which occurrence a single value argument keeps when it is given several times
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverridePolicy {
+    FirstWins,
+    #[default]
+    LastWins,
+}
 
 // Command structure for building parsers manually
 #[derive(Debug, Clone)]
@@ -16,7 +35,25 @@ pub struct Command {
     about: Option<String>,
     args: Vec<ArgInfo>,
     global_args: Vec<ArgInfo>,
+    groups: Vec<ArgGroup>,
     subcommands: Vec<SubcommandInfo>,
+    infer_long_args: bool,
+    infer_subcommands: bool,
+    override_policy: OverridePolicy,
+    collect_errors: bool,
+    messages: &'static dyn Messages,
+    validated: Validated,
+}
+
+// Whether the definition was already checked, set by the first parse of debug builds
+#[derive(Debug, Default)]
+struct Validated(AtomicBool);
+
+impl Clone for Validated {
+    // A clone can still be modified, it is checked again
+    fn clone(&self) -> Self {
+        Self::default()
+    }
 }
 
 impl Command {
@@ -28,16 +65,36 @@ impl Command {
             about: about.map(|v| v.to_string()),
             args: Vec::new(),
             global_args: Vec::new(),
+            groups: Vec::new(),
             subcommands: Vec::new(),
+            infer_long_args: false,
+            infer_subcommands: false,
+            override_policy: OverridePolicy::default(),
+            collect_errors: false,
+            messages: &English,
+            validated: Validated::default(),
         }
     }
 
     pub fn get_name(&self) -> Option<&str> {
-        self.name.as_ref().map(|v| v.as_str())
+        self.name.as_deref()
     }
 
     pub fn get_version(&self) -> Option<&str> {
-        self.version.as_ref().map(|v| v.as_str())
+        self.version.as_deref()
+    }
+
+    // Definitions of the arguments, e.g. for completions of their possible values
+    pub fn get_args(&self) -> &[ArgInfo] {
+        &self.args
+    }
+
+    pub fn get_global_args(&self) -> &[ArgInfo] {
+        &self.global_args
+    }
+
+    pub fn get_subcommands(&self) -> &[SubcommandInfo] {
+        &self.subcommands
     }
 
     pub fn arg(mut self, arg: ArgInfo) -> Self {
@@ -50,103 +107,510 @@ impl Command {
         self
     }
 
+    pub fn group(mut self, group: ArgGroup) -> Self {
+        self.groups.push(group);
+        self
+    }
+
     pub fn subcommand(mut self, subcommand: SubcommandInfo) -> Self {
         self.subcommands.push(subcommand);
         self
     }
 
-    pub fn parse(&mut self, args: &[String]) -> Result<ParsedArgs, ParseError> {
-        let current_args = &mut self.args;
-        let global_args = &mut self.global_args;
-        let subcommands = &mut self.subcommands;
-        Self::parse_with_subcommands(args, current_args, global_args, subcommands)
+    // Accept a unique prefix of a long option, e.g. `--verb` for `--verbose`
+    pub fn infer_long_args(mut self) -> Self {
+        self.infer_long_args = true;
+        self
+    }
+
+    // Accept a unique prefix of a subcommand, e.g. `rem` for `remove`
+    pub fn infer_subcommands(mut self) -> Self {
+        self.infer_subcommands = true;
+        self
+    }
+
+    pub fn override_policy(mut self, policy: OverridePolicy) -> Self {
+        self.override_policy = policy;
+        self
+    }
+
+    // Keep parsing after an error and report all of them at once with an `ErrorKind::Multiple` error
+    pub fn collect_errors(mut self) -> Self {
+        self.collect_errors = true;
+        self
+    }
+
+    // Texts of the help and of the errors, `English` by default
+    pub fn messages(mut self, messages: &'static dyn Messages) -> Self {
+        self.messages = messages;
+        self
+    }
+
+    pub fn get_messages(&self) -> &'static dyn Messages {
+        self.messages
     }
 
-    fn parse_with_subcommands(args: &[String], current_args: &mut [ArgInfo], global_args: &[ArgInfo], current_subcommands: &mut [SubcommandInfo]) -> Result<ParsedArgs, ParseError> {
+    // Settings and arguments the derives follow while building their fields from the parsed arguments
+    pub fn parse_context(&self) -> ParseContext<'_> {
+        ParseContext {
+            collect_errors: self.collect_errors,
+            messages: self.messages,
+            args: &self.args,
+            global_args: &self.global_args,
+            subcommands: &self.subcommands,
+        }
+    }
+
+    // Every mistake in the definition of the arguments and subcommands, checked on all the levels
+    pub fn validate(&self) -> Result<(), Vec<DefinitionError>> {
+        let mut errors = Vec::new();
+        let path = self.name.clone().unwrap_or_else(|| String::from("command"));
+
+        validate_level(&path, &self.args, &self.global_args, &self.subcommands, true, &mut errors);
+
+        if errors.is_empty() {
+            Ok(())
+        }
+        else {
+            Err(errors)
+        }
+    }
+
+    // Panic with every definition error, done before the first parse of debug builds
+    pub fn debug_assert(&self) {
+        if let Err(errors) = self.validate() {
+            let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+            panic!("Invalid command definition:\n{}", errors.join("\n"));
+        }
+    }
+
+    // Parsing never modifies the command, the same command can parse any number of inputs
+    pub fn parse(&self, args: &[String]) -> Result<ParsedArgs, ParseError> {
+        let mut errors = ParseErrors::new();
+        let result = self.parse_collecting(args, &mut errors).map_err(|error| self.complete_error(error))?;
+
+        errors.into_result(result).map_err(|error| self.complete_error(error))
+    }
+
+    // Give the error what only the command knows: its message catalog and the usage line of the level the error
+    // comes from, shown by `ParseError::render`. Help and version requests have no usage line
+    pub fn complete_error(&self, mut error: ParseError) -> ParseError {
+        error.context.messages = self.messages;
+
+        match error.kind {
+            ErrorKind::Help | ErrorKind::Version => {}
+            ErrorKind::Multiple => {
+                let mut errors = ParseErrors::new();
+                for inner in mem::take(&mut error.context.errors) {
+                    errors.push(self.complete_error(inner));
+                }
+                error.context.usage = errors.iter().find_map(|inner| inner.context.usage.clone());
+                error.context.errors = errors;
+            }
+            _ if error.context.usage.is_none() => {
+                error.context.usage = Some(self.get_usage(error.context.subcommand.as_deref()));
+            }
+            _ => {}
+        }
+
+        error
+    }
+
+    // In the collect errors mode, the errors are kept in `errors` and the arguments parsed despite them are returned,
+    // otherwise this stops on the first error like `parse`
+    pub fn parse_collecting(&self, args: &[String], errors: &mut ParseErrors) -> Result<ParsedArgs, ParseError> {
+        // Only loads and stores, the targets without compare and swap have them. Two parses racing only validate
+        // twice. The derives build their command on every parse, so it is validated on every parse
+        #[cfg(debug_assertions)]
+        if !self.validated.0.load(Ordering::Relaxed) {
+            self.debug_assert();
+            self.validated.0.store(true, Ordering::Relaxed);
+        }
+
+        let mut result = self.parse_with_subcommands(args, "", 0, &self.args, &self.subcommands, errors)?;
+
+        // Version requested on the top level command
+        let version_requested = self.args.iter()
+            .chain(self.global_args.iter())
+            .any(|a| a.action == ArgAction::Version && result.contains_key(&a.name));
+
+        if version_requested {
+            let version = match (&self.name, &self.version) {
+                (Some(name), Some(version)) => format!("{} {}", name, version),
+                (None, Some(version)) => version.clone(),
+                (Some(name), None) => name.clone(),
+                (None, None) => String::new(),
+            };
+
+            return Err(ParseError::new(ErrorKind::Version).message(version));
+        }
+
+        // The conditional defaults are checked by the constraints like any given value. The global ones are added once,
+        // on the top level where they are defined, when no level has them
+        let levels: Vec<&ParsedArgs> = core::iter::successors(Some(&result), |parsed| parsed.get_subcommand().map(|(_, sub_result)| sub_result)).collect();
+        let global_defaults: Vec<(String, String)> = self.global_args.iter()
+            .filter_map(|arg_info| conditional_default(arg_info, &levels).map(|default| (arg_info.name.clone(), default)))
+            .collect();
+
+        for (name, default) in global_defaults {
+            result.insert(name, default);
+        }
+
+        apply_conditional_defaults(&mut result, &self.args, &self.subcommands);
+        self.check_constraints(&result, errors)?;
+
+        Ok(result)
+    }
+
+    // Report every missing required argument along the parsed subcommand chain at once, after any invalid value or conflict between arguments
+    fn check_constraints(&self, result: &ParsedArgs, errors: &mut ParseErrors) -> Result<(), ParseError> {
+        let mut missing = Vec::new();
+        let mut violations = Vec::new();
+        let mut help_requested = false;
+        let mut usage = String::new();
+        let mut path = self.name.clone().unwrap_or_default();
+        let mut subcommand_path = String::new();
+        let mut level_args: &[ArgInfo] = &self.args;
+        let mut level_groups: &[ArgGroup] = &self.groups;
+        let mut level_subcommands: &[SubcommandInfo] = &self.subcommands;
+        let mut level_result = result;
+
+        loop {
+            help_requested |= level_args.iter()
+                .chain(self.global_args.iter())
+                .any(|a| a.action == ArgAction::Help && level_result.is_present(&a.name));
+
+            for arg_info in level_args.iter().filter(|a| is_required(a, level_result) && !level_result.is_present(&a.name)) {
+                missing.push(arg_info.display_name());
+            }
+
+            let known_args: Vec<&ArgInfo> = level_args.iter().chain(self.global_args.iter()).collect();
+
+            for arg_info in known_args.iter().filter(|a| level_result.is_present(&a.name)) {
+                if !self.collect_errors && !violations.is_empty() {
+                    break;
+                }
+
+                let out_of_range = arg_info.range.and_then(|range| level_result.get_all(&arg_info.name)
+                    .into_iter()
+                    .find(|value| !range.contains(value))
+                    .map(|value| (range, value))
+                );
+
+                let impossible_value = level_result.get_all(&arg_info.name)
+                    .into_iter()
+                    .find(|value| !arg_info.possible_values.is_empty() && !arg_info.possible_values.iter().any(|possible| possible == value));
+
+                let conflicting = arg_info.conflicts_with.iter()
+                    .filter(|name| level_result.is_present(name))
+                    .find_map(|name| known_args.iter().find(|a| a.name == **name));
+
+                let unmet = arg_info.requires.iter()
+                    .filter(|name| !level_result.is_present(name))
+                    .find_map(|name| known_args.iter().find(|a| a.name == **name));
+
+                if let Some(value) = impossible_value {
+                    violations.push(ParseError::new(ErrorKind::UnknownEnumVariant)
+                        .arg(arg_info.display_name())
+                        .value(value)
+                        .expected(arg_info.possible_values.clone())
+                        .suggestion(did_you_mean(value, arg_info.possible_values.iter().map(String::as_str)))
+                        .subcommand(&subcommand_path));
+                }
+                else if let Some((range, value)) = out_of_range {
+                    violations.push(ParseError::new(ErrorKind::OutOfRange)
+                        .arg(arg_info.display_name())
+                        .value(value)
+                        .expected(vec![range.to_string()])
+                        .subcommand(&subcommand_path));
+                }
+                else if let Some(other) = conflicting {
+                    violations.push(ParseError::new(ErrorKind::ArgumentConflict)
+                        .arg(arg_info.display_name())
+                        .expected(vec![other.display_name()])
+                        .subcommand(&subcommand_path));
+                }
+                else if let Some(required) = unmet {
+                    violations.push(ParseError::new(ErrorKind::MissingRequirement)
+                        .arg(arg_info.display_name())
+                        .expected(vec![required.display_name()])
+                        .subcommand(&subcommand_path));
+                }
+            }
+
+            for group in level_groups {
+                let present: Vec<&ArgInfo> = group.args.iter()
+                    .filter(|name| level_result.is_present(name))
+                    .filter_map(|name| known_args.iter().find(|a| a.name == **name).copied())
+                    .collect();
+
+                if present.len() > 1 && !group.multiple && (self.collect_errors || violations.is_empty()) {
+                    violations.push(ParseError::new(ErrorKind::GroupConflict)
+                        .group(group.name.clone())
+                        .expected(present.iter().map(|a| a.display_name()).collect())
+                        .subcommand(&subcommand_path));
+                }
+                else if present.is_empty() && group.required {
+                    let names: Vec<String> = group.args.iter()
+                        .filter_map(|name| known_args.iter().find(|a| a.name == *name))
+                        .map(|a| a.display_name())
+                        .collect();
+
+                    missing.push(format!("<{}>", names.join("|")));
+                }
+            }
+
+            usage.clear();
+            get_usage(&mut usage, self.messages, &path, level_args, &self.global_args, level_groups, level_subcommands);
+
+            let Some((name, sub_result)) = level_result.get_subcommand()
+            else {
+                break;
+            };
+
+            let Some(subcommand_info) = level_subcommands.iter().find(|sc| sc.name == name)
+            else {
+                break;
+            };
+
+            path = match path.is_empty() {
+                true => subcommand_info.name.clone(),
+                false => format!("{} {}", path, subcommand_info.name),
+            };
+            subcommand_path = match subcommand_path.is_empty() {
+                true => subcommand_info.name.clone(),
+                false => format!("{} {}", subcommand_path, subcommand_info.name),
+            };
+            level_args = &subcommand_info.args;
+            level_groups = &subcommand_info.groups;
+            level_subcommands = &subcommand_info.subcommands;
+            level_result = sub_result;
+        }
+
+        // Global arguments can be given at any level
+        for arg_info in self.global_args.iter().filter(|a| a.required) {
+            let mut present = false;
+            let mut level_result = Some(result);
+
+            while let Some(parsed) = level_result {
+                present |= parsed.is_present(&arg_info.name);
+                level_result = parsed.get_subcommand().map(|(_, sub_result)| sub_result);
+            }
+
+            if !present {
+                missing.push(arg_info.display_name());
+            }
+        }
+
+        if help_requested {
+            // An explicit help request wins over the errors of the input
+            errors.clear();
+            return Ok(());
+        }
+
+        if !missing.is_empty() {
+            violations.push(ParseError::new(ErrorKind::MissingArguments)
+                .expected(missing)
+                .usage(usage)
+                .subcommand(&subcommand_path));
+        }
+
+        for violation in violations {
+            errors.recover(violation, self.collect_errors)?;
+        }
+
+        Ok(())
+    }
+
+    // `path` names the subcommands leading to this level and `offset` is the index of its first argument in the whole
+    // input, for the context of the errors
+    fn parse_with_subcommands(&self, args: &[String], path: &str, offset: usize, current_args: &[ArgInfo], current_subcommands: &[SubcommandInfo], errors: &mut ParseErrors) -> Result<ParsedArgs, ParseError> {
+        let global_args: &[ArgInfo] = &self.global_args;
         let mut result = ParsedArgs::new();
+        // Positionals are filled in declaration order, this is the per-parse state
+        let positional_args = ordered_positionals(current_args);
+        let mut positional_index = 0;
+        let mut options_ended = false;
+        // Option which received its maximum number of values on the previous token
+        let mut full_option: Option<&ArgInfo> = None;
         let mut i = 0;
 
         while i < args.len() {
             let arg = &args[i];
+            let previous_full_option = full_option.take();
+
+            if options_ended {
+                // Everything after `--` goes to the `last` argument, or to the remaining positionals
+                if let Some(arg_info) = current_args.iter().find(|a| a.last) {
+                    result.insert(arg_info.name.clone(), arg.clone());
+                }
+                else if let Some(arg_info) = positional_args.get(positional_index) {
+                    insert_positional(&mut result, arg_info, arg, &mut positional_index);
+                }
+                else {
+                    errors.recover(ParseError::new(ErrorKind::UnknownArgument).arg(arg.clone()).subcommand(path).token_index(offset + i), self.collect_errors)?;
+                }
+
+                i += 1;
+                continue;
+            }
+
+            if arg == "--" {
+                // End of options
+                options_ended = true;
+                i += 1;
+                continue;
+            }
+
+            // Determine argument metadata (current + global)
+            let has_digit_short = current_args.iter().chain(global_args.iter()).any(|a| a.short.is_some_and(|c| c.is_ascii_digit()));
+            let is_hyphen_positional = arg.starts_with('-') && positional_args.get(positional_index)
+                .is_some_and(|a| accepts_hyphen_value(arg, a, has_digit_short) && !is_known_option(arg, current_args, global_args));
 
             // Check if this is a subcommand
-            if !arg.starts_with('-') {
-                if let Some(subcommand_info) = current_subcommands.iter_mut().find(|sc| sc.name == *arg) {
+            if !arg.starts_with('-') || arg == "-" || is_hyphen_positional {
+                let subcommand_names: Vec<(&str, &SubcommandInfo)> = current_subcommands.iter().map(|sc| (sc.name.as_str(), sc)).collect();
+
+                let subcommand_info = match resolve_name(arg, &subcommand_names, self.infer_subcommands) {
+                    Ok(subcommand_info) => subcommand_info,
+                    Err(candidates) => {
+                        errors.recover(ParseError::new(ErrorKind::Ambiguous)
+                            .arg(arg.clone())
+                            .expected(candidates.iter().map(|candidate| candidate.to_string()).collect())
+                            .subcommand(path).token_index(offset + i), self.collect_errors)?;
+                        i += 1;
+                        continue;
+                    }
+                };
+
+                if let Some(subcommand_info) = subcommand_info {
                     // Parse the remaining arguments as subcommand arguments
                     let remaining_args = &args[i + 1..];
 
-                    let subcommand_result = match Self::parse_with_subcommands(remaining_args, &mut subcommand_info.args, global_args, &mut subcommand_info.subcommands) {
-                        Ok(subcommand_result) => subcommand_result,
-                        Err(e) => return Err(e),
+                    let subcommand_path = match path.is_empty() {
+                        true => subcommand_info.name.clone(),
+                        false => format!("{} {}", path, subcommand_info.name),
                     };
+                    let subcommand_result = self.parse_with_subcommands(remaining_args, &subcommand_path, offset + i + 1, &subcommand_info.args, &subcommand_info.subcommands, errors)?;
 
-                    result.set_subcommand(arg.clone(), subcommand_result);
+                    result.set_subcommand(subcommand_info.name.clone(), subcommand_result);
 
                     // Stop parsing after subcommand
                     break;
                 }
-                else if let Some(arg_info) = current_args.iter_mut().find(|a| a.short.is_none() && a.long.is_none() && !a.used) {
+                else if let Some(arg_info) = positional_args.get(positional_index) {
                     // Positional argument
-                    result.insert(arg_info.name.clone(), arg.clone());
-                    arg_info.used = true;
+                    insert_positional(&mut result, arg_info, arg, &mut positional_index);
+                }
+                else if let Some(option) = previous_full_option {
+                    // Extra value given to a multiple values option
+                    errors.recover(ParseError::new(ErrorKind::TooManyValues)
+                        .arg(option.display_name())
+                        .value(arg.clone())
+                        .max_values(option.value_range().max)
+                        .subcommand(path).token_index(offset + i), self.collect_errors)?;
                 }
                 else {
-                    // Unknown positional argument -> error or ignore
-                    return Err(ParseError::UnknownArgument(arg.clone()));
+                    // Unknown positional argument, likely a mistyped subcommand
+                    let suggestion = did_you_mean(arg, current_subcommands.iter().map(|sc| sc.name.as_str()));
+                    errors.recover(ParseError::new(ErrorKind::UnknownArgument).arg(arg.clone()).suggestion(suggestion).subcommand(path).token_index(offset + i), self.collect_errors)?;
                 }
+
+                i += 1;
+                continue;
             }
 
-            // Determine argument metadata (current + global)
             let all_args: Vec<&ArgInfo> = current_args.iter().chain(global_args.iter()).collect();
 
-            if arg.starts_with("--") {
-                // Long argument
-                let arg_name = &arg[2..];
-                if let Some(arg_info) = all_args.iter().find(|a| a.long.as_ref().map_or(false, |l| l == arg_name)) {
-                    if arg_info.count {
-                        // increment once for each occurrence
-                        result.increment(arg_info.name.clone());
+            if let Some(arg_name) = arg.strip_prefix("--") {
+                // Long argument, the value can be attached with `--name=value`
+                let (arg_name, attached_value) = match arg_name.split_once('=') {
+                    Some((arg_name, value)) => (arg_name, Some(value)),
+                    None => (arg_name, None),
+                };
+
+                // Long names with the `no-<long>` of negatable flags, and whether they are negated
+                let long_targets: Vec<(String, &ArgInfo, bool)> = all_args.iter()
+                    .filter_map(|a| a.long.as_ref().map(|long| (long, *a)))
+                    .flat_map(|(long, a)| {
+                        let negated = a.negatable.then(|| (format!("no-{}", long), a, true));
+                        core::iter::once((long.clone(), a, false)).chain(negated)
+                    })
+                    .collect();
+                let long_names: Vec<(&str, &(String, &ArgInfo, bool))> = long_targets.iter().map(|target| (target.0.as_str(), target)).collect();
+
+                let target = match resolve_name(arg_name, &long_names, self.infer_long_args) {
+                    Ok(target) => target,
+                    Err(candidates) => {
+                        errors.recover(ParseError::new(ErrorKind::Ambiguous)
+                            .arg(format!("--{}", arg_name))
+                            .expected(candidates.iter().map(|candidate| format!("--{}", candidate)).collect())
+                            .subcommand(path).token_index(offset + i), self.collect_errors)?;
+                        i += 1;
+                        continue;
                     }
-                    else if is_bool_flag(args, i) {
-                        // Boolean flag - just mark as present
-                        result.insert_flag(arg_info.name.clone());
+                };
+
+                if let Some((_, arg_info, negated)) = target {
+                    let consumed = match negated {
+                        true => apply_negation(&mut result, arg_info, attached_value, self.override_policy).map(|_| 0),
+                        false => apply_action(&mut result, arg_info, attached_value, args, i, has_digit_short, self.override_policy),
+                    };
+
+                    match consumed {
+                        Ok(consumed) => i += consumed,
+                        Err(error) => errors.recover(error.subcommand(path).token_index(offset + i), self.collect_errors)?,
                     }
-                    else {
-                        // Value argument
-                        i += 1;
-                        result.insert(arg_info.name.clone(), args[i].clone());
+
+                    if is_full(&result, arg_info) {
+                        full_option = Some(arg_info);
                     }
                 }
                 else {
-                    return Err(ParseError::UnknownArgument(arg_name.to_string()));
+                    let suggestion = did_you_mean(arg_name, long_targets.iter().map(|target| target.0.as_str()))
+                        .map(|long| format!("--{}", long));
+                    errors.recover(ParseError::new(ErrorKind::UnknownArgument).arg(arg_name).suggestion(suggestion).subcommand(path).token_index(offset + i), self.collect_errors)?;
                 }
             }
             else if arg.starts_with('-') && arg.len() >= 2 {
                 // Handle clusters: e.g. -vvv or -abc
-                for short_char in arg.chars().skip(1) {
+                for (index, short_char) in arg.char_indices().skip(1) {
                     if let Some(arg_info) = all_args.iter().find(|a| a.short == Some(short_char)) {
-                        if arg_info.count {
-                            // increment once for each occurrence
-                            result.increment(arg_info.name.clone());
-                        }
-                        else if is_bool_flag(args, i) {
-                            result.insert_flag(arg_info.name.clone());
-                        }
-                        else {
-                            let rest: String = arg.chars().skip_while(|c| *c != short_char).skip(1).collect();
-                            if !rest.is_empty() {
-                                result.insert(arg_info.name.clone(), rest);
+                        let rest = &arg[index + short_char.len_utf8()..];
+
+                        if arg_info.action.takes_value() {
+                            // The rest of the cluster is the value, e.g. `-n5` or `-n=5`
+                            let attached_value = match rest.is_empty() {
+                                true => None,
+                                false => Some(rest.strip_prefix('=').unwrap_or(rest)),
+                            };
+
+                            match apply_action(&mut result, arg_info, attached_value, args, i, has_digit_short, self.override_policy) {
+                                Ok(consumed) => i += consumed,
+                                Err(error) => errors.recover(error.subcommand(path).token_index(offset + i), self.collect_errors)?,
                             }
-                            else {
-                                i += 1;
-                                result.insert(arg_info.name.clone(), args[i].clone());
+
+                            if is_full(&result, arg_info) {
+                                full_option = Some(arg_info);
                             }
                             // stop further processing of this cluster
                             break;
                         }
+                        else if let Some(value) = rest.strip_prefix('=') {
+                            // Value attached with `-f=false`
+                            if let Err(error) = apply_action(&mut result, arg_info, Some(value), args, i, has_digit_short, self.override_policy) {
+                                errors.recover(error.subcommand(path).token_index(offset + i), self.collect_errors)?;
+                            }
+                            break;
+                        }
+                        else if let Err(error) = apply_action(&mut result, arg_info, None, args, i, has_digit_short, self.override_policy) {
+                            errors.recover(error.subcommand(path).token_index(offset + i), self.collect_errors)?;
+                        }
                     }
                     else {
-                        return Err(ParseError::UnknownArgument(short_char.to_string()));
+                        // The rest of the cluster can't be trusted after an unknown short
+                        errors.recover(ParseError::new(ErrorKind::UnknownArgument).arg(short_char.to_string()).subcommand(path).token_index(offset + i), self.collect_errors)?;
+                        break;
                     }
                 }
             }
@@ -186,25 +650,249 @@ impl Command {
             writeln!(out).unwrap();
         }
 
-        get_help(&mut out, None, &self.args, &self.global_args, &self.subcommands);
+        get_help(&mut out, self.messages, None, &self.args, &self.global_args, &self.groups, &self.subcommands);
 
         out
     }
+
+    // Usage line of the subcommand at `subcommand` (e.g. `remote add`), of the command itself for `None`
+    pub fn get_usage(&self, subcommand: Option<&str>) -> String {
+        let mut path = self.name.clone().unwrap_or_default();
+        let mut level_args: &[ArgInfo] = &self.args;
+        let mut level_groups: &[ArgGroup] = &self.groups;
+        let mut level_subcommands: &[SubcommandInfo] = &self.subcommands;
+
+        for name in subcommand.unwrap_or_default().split_whitespace() {
+            let Some(subcommand_info) = level_subcommands.iter().find(|sc| sc.name == name)
+            else {
+                break;
+            };
+
+            path = match path.is_empty() {
+                true => subcommand_info.name.clone(),
+                false => format!("{} {}", path, subcommand_info.name),
+            };
+            level_args = &subcommand_info.args;
+            level_groups = &subcommand_info.groups;
+            level_subcommands = &subcommand_info.subcommands;
+        }
+
+        let mut out = String::new();
+        get_usage(&mut out, self.messages, &path, level_args, &self.global_args, level_groups, level_subcommands);
+        out
+    }
 }
 
-// Helper function to determine if an argument is a boolean flag
-fn is_bool_flag(args: &[String], current_index: usize) -> bool {
-    // If the next argument starts with '-' or we're at the end, treat as boolean
-    let next_index = current_index + 1;
-    if next_index >= args.len() {
-        return true;
+// Positional arguments have neither short nor long, `last` ones are only filled after `--`
+fn is_positional(arg: &ArgInfo) -> bool {
+    arg.short.is_none() && arg.long.is_none() && !arg.last
+}
+
+// Plain or conditional requirement, evaluated against the parsed arguments of the same level
+fn is_required(arg_info: &ArgInfo, parsed: &ParsedArgs) -> bool {
+    let required_if_eq = arg_info.required_if_eq.iter()
+        .any(|(name, value)| parsed.get_all(name).contains(&value.as_str()));
+
+    let required_unless_present = !arg_info.required_unless_present.is_empty()
+        && !arg_info.required_unless_present.iter().any(|name| parsed.is_present(name));
+
+    arg_info.required || required_if_eq || required_unless_present
+}
+
+// Fill the missing arguments having a default for the value of another argument, at every parsed level
+fn apply_conditional_defaults(result: &mut ParsedArgs, args: &[ArgInfo], subcommands: &[SubcommandInfo]) {
+    for arg_info in args {
+        if let Some(default) = conditional_default(arg_info, &[result]) {
+            result.insert(arg_info.name.clone(), default);
+        }
+    }
+
+    if let Some((name, sub_result)) = &mut result.subcommand
+        && let Some(subcommand_info) = subcommands.iter().find(|sc| sc.name == *name) {
+        apply_conditional_defaults(sub_result, &subcommand_info.args, &subcommand_info.subcommands);
+    }
+}
+
+// Default of an argument missing from `levels` for the value another argument has in them
+fn conditional_default(arg_info: &ArgInfo, levels: &[&ParsedArgs]) -> Option<String> {
+    if levels.iter().any(|parsed| parsed.is_present(&arg_info.name)) {
+        return None;
+    }
+
+    arg_info.default_value_if.iter()
+        .find(|(name, value, _)| levels.iter().any(|parsed| parsed.get_all(name).contains(&value.as_str())))
+        .map(|(_, _, default)| default.clone())
+}
+
+// Find an exact name, or a unique prefix when inference is enabled, an ambiguous prefix gives back the matching names
+fn resolve_name<'a, 'b, T>(given: &str, candidates: &[(&'b str, &'a T)], infer: bool) -> Result<Option<&'a T>, Vec<&'b str>> {
+    if let Some((_, value)) = candidates.iter().find(|(name, _)| *name == given) {
+        return Ok(Some(value));
+    }
+
+    if !infer || given.is_empty() {
+        return Ok(None);
+    }
+
+    let matches: Vec<&(&str, &T)> = candidates.iter().filter(|(name, _)| name.starts_with(given)).collect();
+
+    match matches.as_slice() {
+        [] => Ok(None),
+        [(_, value)] => Ok(Some(value)),
+        _ => Err(matches.iter().map(|(name, _)| *name).collect()),
+    }
+}
+
+// Positional arguments sorted by their explicit index, the ones without index follow in declaration order
+pub(crate) fn ordered_positionals(args: &[ArgInfo]) -> Vec<&ArgInfo> {
+    let mut positional_args: Vec<&ArgInfo> = args.iter().filter(|a| is_positional(a)).collect();
+    positional_args.sort_by_key(|a| a.index.unwrap_or(usize::MAX));
+    positional_args
+}
+
+// A variadic positional keeps collecting values, the others move on to the next positional
+fn insert_positional(result: &mut ParsedArgs, arg_info: &ArgInfo, value: &str, positional_index: &mut usize) {
+    result.insert(arg_info.name.clone(), value.to_string());
+
+    if arg_info.action != ArgAction::Append {
+        *positional_index += 1;
+    }
+}
+
+// Record one occurrence of an option according to its action, returns the number of consumed tokens after it
+fn apply_action(result: &mut ParsedArgs, arg_info: &ArgInfo, attached_value: Option<&str>, args: &[String], current_index: usize, has_digit_short: bool, policy: OverridePolicy) -> Result<usize, ParseError> {
+    let mut consumed = 0;
+
+    match arg_info.action {
+        ArgAction::SetTrue | ArgAction::SetFalse => {
+            let value = match attached_value {
+                Some(value) => bool::from_arg(value).map_err(|error| error.arg(arg_info.display_name()))?,
+                None => arg_info.action == ArgAction::SetTrue,
+            };
+
+            store(result, arg_info, vec![value.to_string()], policy);
+        }
+        ArgAction::Count | ArgAction::Help | ArgAction::Version => {
+            if let Some(value) = attached_value {
+                return Err(ParseError::new(ErrorKind::UnexpectedValue).arg(arg_info.display_name()).value(value));
+            }
+
+            match arg_info.action {
+                // increment once for each occurrence
+                ArgAction::Count => result.increment(arg_info.name.clone()),
+                _ => result.insert_flag(arg_info.name.clone()),
+            }
+        }
+        ArgAction::Set | ArgAction::Append => {
+            let range = arg_info.value_range();
+            let mut values: Vec<String> = attached_value.map(String::from).into_iter().collect();
+
+            // Values are taken from the next tokens, up to the maximum or until the next option
+            while values.len() < range.max {
+                match args.get(current_index + 1 + consumed) {
+                    Some(next_arg) if !next_arg.starts_with('-') || accepts_hyphen_value(next_arg, arg_info, has_digit_short) => {
+                        values.push(next_arg.clone());
+                        consumed += 1;
+                    }
+                    _ => break,
+                }
+            }
+
+            if values.len() < range.min {
+                return match values.is_empty() {
+                    true => Err(ParseError::new(ErrorKind::MissingValue).arg(arg_info.display_name())),
+                    false => Err(ParseError::new(ErrorKind::TooFewValues)
+                        .arg(arg_info.display_name())
+                        .min_values(range.min)
+                        .num_values(values.len())),
+                };
+            }
+
+            store(result, arg_info, values, policy);
+        }
+    }
+
+    apply_overrides(result, arg_info);
+
+    Ok(consumed)
+}
+
+// `--no-<long>` sets a negatable flag to false
+fn apply_negation(result: &mut ParsedArgs, arg_info: &ArgInfo, attached_value: Option<&str>, policy: OverridePolicy) -> Result<(), ParseError> {
+    if let Some(value) = attached_value {
+        return Err(ParseError::new(ErrorKind::UnexpectedValue)
+            .arg(format!("--no-{}", arg_info.long.as_deref().unwrap_or_default()))
+            .value(value));
     }
 
-    let next_arg = &args[next_index];
-    if next_arg.starts_with('-') {
+    store(result, arg_info, vec![false.to_string()], policy);
+    apply_overrides(result, arg_info);
+
+    Ok(())
+}
+
+// Record the values of one occurrence, a single value argument only keeps one occurrence according to the policy
+fn store(result: &mut ParsedArgs, arg_info: &ArgInfo, values: Vec<String>, policy: OverridePolicy) {
+    if arg_info.action != ArgAction::Append && result.contains_key(&arg_info.name) {
+        match policy {
+            OverridePolicy::FirstWins => return,
+            OverridePolicy::LastWins => result.remove(&arg_info.name),
+        }
+    }
+
+    result.insert_occurrence(arg_info.name.clone(), values);
+}
+
+// Discard the values of the arguments overridden by this one
+fn apply_overrides(result: &mut ParsedArgs, arg_info: &ArgInfo) {
+    for name in arg_info.overrides_with.iter().filter(|name| **name != arg_info.name) {
+        result.remove(name);
+    }
+}
+
+// Whether the last occurrence of a multiple values option cannot take any more values
+fn is_full(result: &ParsedArgs, arg_info: &ArgInfo) -> bool {
+    let range = arg_info.value_range();
+
+    range.takes_multiple() && result.occurrences.get(&arg_info.name)
+        .and_then(|occurrences| occurrences.last())
+        .is_some_and(|count| *count == range.max)
+}
+
+// Whether a token starting with '-' can be used as a value for this argument
+fn accepts_hyphen_value(value: &str, arg_info: &ArgInfo, has_digit_short: bool) -> bool {
+    if value == "--" {
+        return false;
+    }
+
+    // A lone `-` is a value, usually standing for stdin or stdout
+    if value == "-" {
         return true;
     }
 
-    // For explicit boolean values
-    matches!(next_arg.to_lowercase().as_str(), "true" | "false" | "1" | "0" | "yes" | "no" | "on" | "off")
-}
\ No newline at end of file
+    // Negative numbers are values, unless a short like `-5` exists
+    arg_info.allow_hyphen_values || (!has_digit_short && is_negative_number(value))
+}
+
+fn is_negative_number(value: &str) -> bool {
+    match value.strip_prefix('-') {
+        Some(number) => number.starts_with(|c: char| c.is_ascii_digit() || c == '.') && number.parse::<f64>().is_ok(),
+        None => false,
+    }
+}
+
+// Whether a token starting with '-' names one of the known options
+fn is_known_option(arg: &str, current_args: &[ArgInfo], global_args: &[ArgInfo]) -> bool {
+    let mut all_args = current_args.iter().chain(global_args.iter());
+
+    match arg.strip_prefix("--") {
+        Some(long) => {
+            let long = long.split_once('=').map_or(long, |(long, _)| long);
+            all_args.any(|a| a.long.as_deref() == Some(long) || (a.negatable && a.long.as_deref() == long.strip_prefix("no-")))
+        },
+        None => {
+            let short = arg.chars().nth(1);
+            all_args.any(|a| a.short.is_some() && a.short == short)
+        }
+    }
+}
@@ -1,31 +1,497 @@
-use alloc::string::String;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::error::Error;
 use core::fmt;
 use core::fmt::Display;
+use core::mem;
+use core::ops::Range;
+use crate::messages::{English, Messages};
+use crate::parser::Token;
 
-// Error types
-#[derive(Debug)]
-pub enum ParseError {
+// What went wrong, the details are in the `ErrorContext` of the `ParseError`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
     EmptyInput,
-    Help(String),
-    MissingArgument(String),
-    InvalidValue(String),
-    UnknownArgument(String),
+    // `message` is the help text
+    Help,
+    // `message` is the version text
+    Version,
+    MissingArgument,
+    // `expected` lists the missing arguments
+    MissingArguments,
+    MissingValue,
+    TooFewValues,
+    TooManyValues,
+    // `value_type` is the type the value could not be converted to, otherwise `message` says why it was rejected
+    InvalidValue,
+    // `message` is the error of the argument's value parser
+    InvalidArgumentValue,
+    // `expected` holds the allowed range
+    OutOfRange,
+    // `arg` is a flag, `value` the value attached to it
+    UnexpectedValue,
+    UnknownArgument,
     UnknownSubcommand,
-    InvalidFormat(String),
-    UnknownEnumVariant(String, String),
+    // `expected` lists the subcommands of the level
+    MissingSubcommand,
+    // `expected` lists the arguments used together
+    GroupConflict,
+    // `expected` holds the argument it conflicts with
+    ArgumentConflict,
+    // `expected` holds the required argument
+    MissingRequirement,
+    // `expected` lists the matching names
+    Ambiguous,
+    // `span` starts at the quote
+    UnclosedQuote,
+    // `expected` lists the possible values
+    UnknownEnumVariant,
+    // `errors` holds every error of the input, when the command collects errors
+    Multiple,
+}
+
+// Error of a parse, only the fields of the context relevant to its kind are set
+#[derive(Debug)]
+pub struct ParseError {
+    pub kind: ErrorKind,
+    // Boxed so that a `Result<T, ParseError>` stays small
+    pub context: Box<ErrorContext>,
+}
+
+#[derive(Debug)]
+pub struct ErrorContext {
+    // Argument as typed by the user, e.g. `--name`
+    pub arg: Option<String>,
+    // Value given by the user
+    pub value: Option<String>,
+    // Type the value was converted to, e.g. `u32`
+    pub value_type: Option<&'static str>,
+    // Expected values or arguments, depending on the kind
+    pub expected: Vec<String>,
+    pub group: Option<String>,
+    // Names of the subcommands leading to the error, e.g. `remote add`
+    pub subcommand: Option<String>,
+    pub usage: Option<String>,
+    pub message: Option<String>,
+    // Closest known name to a mistyped one, shown as "did you mean ...?"
+    pub suggestion: Option<String>,
+    pub min_values: Option<usize>,
+    pub max_values: Option<usize>,
+    // Number of values given
+    pub num_values: Option<usize>,
+    // Position of the offending argument in the parsed arguments, known for the errors of the command's parse
+    pub token_index: Option<usize>,
+    // Byte range of the offending argument in the parsed line, only known for `Parser::parse_str`
+    pub span: Option<Range<usize>>,
+    pub errors: ParseErrors,
+    // Catalog the error is written with, set by `Command::complete_error`
+    pub messages: &'static dyn Messages,
+    // Error of the conversion that rejected the value, e.g. a `ParseIntError` or the error of a `TryFromArg`
+    pub source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl ParseError {
+    pub fn new(kind: ErrorKind) -> Self {
+        Self {
+            kind,
+            context: Box::new(ErrorContext {
+                arg: None,
+                value: None,
+                value_type: None,
+                expected: Vec::new(),
+                group: None,
+                subcommand: None,
+                usage: None,
+                message: None,
+                suggestion: None,
+                min_values: None,
+                max_values: None,
+                num_values: None,
+                token_index: None,
+                span: None,
+                errors: ParseErrors::new(),
+                messages: &English,
+                source: None,
+            }),
+        }
+    }
+
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.context.arg = Some(arg.into());
+        self
+    }
+
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        self.context.value = Some(value.into());
+        self
+    }
+
+    pub fn value_type(mut self, value_type: &'static str) -> Self {
+        self.context.value_type = Some(value_type);
+        self
+    }
+
+    pub fn expected(mut self, expected: Vec<String>) -> Self {
+        self.context.expected = expected;
+        self
+    }
+
+    pub fn group(mut self, group: impl Into<String>) -> Self {
+        self.context.group = Some(group.into());
+        self
+    }
+
+    // Keeps the subcommand path of an error coming from a deeper level, the errors of a `Multiple` get it as well
+    pub fn subcommand(mut self, subcommand: &str) -> Self {
+        if self.kind == ErrorKind::Multiple {
+            let mut errors = ParseErrors::new();
+            for error in mem::take(&mut self.context.errors) {
+                errors.push(error.subcommand(subcommand));
+            }
+            self.context.errors = errors;
+        }
+
+        if self.context.subcommand.is_none() && !subcommand.is_empty() {
+            self.context.subcommand = Some(String::from(subcommand));
+        }
+        self
+    }
+
+    pub fn usage(mut self, usage: impl Into<String>) -> Self {
+        self.context.usage = Some(usage.into());
+        self
+    }
+
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.context.message = Some(message.into());
+        self
+    }
+
+    // Takes the result of `suggestion::did_you_mean` as is
+    pub fn suggestion(mut self, suggestion: Option<String>) -> Self {
+        self.context.suggestion = suggestion;
+        self
+    }
+
+    pub fn min_values(mut self, min_values: usize) -> Self {
+        self.context.min_values = Some(min_values);
+        self
+    }
+
+    pub fn max_values(mut self, max_values: usize) -> Self {
+        self.context.max_values = Some(max_values);
+        self
+    }
+
+    pub fn num_values(mut self, num_values: usize) -> Self {
+        self.context.num_values = Some(num_values);
+        self
+    }
+
+    // Keeps the index of an error coming from a deeper level, like `subcommand`
+    pub fn token_index(mut self, token_index: usize) -> Self {
+        if self.context.token_index.is_none() {
+            self.context.token_index = Some(token_index);
+        }
+        self
+    }
+
+    pub fn span(mut self, span: Range<usize>) -> Self {
+        self.context.span = Some(span);
+        self
+    }
+
+    pub fn errors(mut self, errors: ParseErrors) -> Self {
+        self.context.errors = errors;
+        self
+    }
+
+    pub fn messages(mut self, messages: &'static dyn Messages) -> Self {
+        self.context.messages = messages;
+        self
+    }
+
+    // Not named `source` to keep `Error::source` callable. Any error type is accepted, as well as a `&str` or a `String`
+    pub fn with_source(mut self, source: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        self.context.source = Some(source.into());
+        self
+    }
+
+    // Find the span of the argument the error is about in the tokens of the line, the value when the error has one.
+    // The token the parse stopped on is used when known, the text of the argument is only searched for the errors
+    // found after the parse, e.g. by the conversion of a field
+    pub fn locate(mut self, tokens: &[Token]) -> Self {
+        if self.kind == ErrorKind::Multiple {
+            let mut errors = ParseErrors::new();
+            for error in mem::take(&mut self.context.errors) {
+                errors.push(error.locate(tokens));
+            }
+            self.context.errors = errors;
+            return self;
+        }
+
+        // Missing arguments and help requests are not about anything in the line
+        if self.context.span.is_some() || matches!(self.kind, ErrorKind::Help | ErrorKind::Version | ErrorKind::MissingArgument | ErrorKind::MissingArguments | ErrorKind::MissingSubcommand) {
+            return self;
+        }
+
+        let arg_index = match self.context.token_index {
+            Some(index) if index < tokens.len() => Some(index),
+            _ => self.context.arg.as_deref().and_then(|arg| tokens.iter().position(|token| names_arg(&token.text, arg))),
+        };
+        let value_span = self.context.value.as_deref().and_then(|value| {
+            tokens[arg_index.unwrap_or_default()..].iter().find_map(|token| value_span(token, value))
+        });
+
+        self.context.span = value_span.or_else(|| arg_index.map(|i| tokens[i].span.clone()));
+        self
+    }
+
+    // The error followed by the line with the offending argument underlined:
+    //
+    // Unknown argument: verbos, did you mean '--verbose'?
+    //   ls --verbos
+    //      ^^^^^^^^
+    pub fn render_source(&self, input: &str) -> String {
+        if self.kind == ErrorKind::Multiple {
+            let rendered: Vec<String> = self.context.errors.iter().map(|error| error.render_source(input)).collect();
+            return rendered.join("\n");
+        }
+
+        let mut out = self.to_string();
+
+        if let Some(span) = &self.context.span && let (Some(before), Some(token)) = (input.get(..span.start), input.get(span.clone())) {
+            let line = input.trim_end();
+            let column = before.chars().count();
+            let width = token.chars().count().max(1);
+            out.push_str(&format!("\n  {}\n  {}{}", line, " ".repeat(column), "^".repeat(width)));
+        }
+
+        out
+    }
+
+    // Full report for the user, in the style of clap:
+    //
+    // error: Unknown argument: verbos, did you mean '--verbose'?
+    //
+    // Usage: app [OPTIONS]
+    //
+    // For more information, try '--help'
+    //
+    // Help and version requests are only their text. The usage line is set by `Command::complete_error`
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        match self.kind {
+            ErrorKind::Help | ErrorKind::Version => return self.context.message.clone().unwrap_or_default(),
+            ErrorKind::Multiple => {
+                for error in self.context.errors.iter() {
+                    out.push_str(&format!("{}: {}\n", self.context.messages.error_prefix(), Summary(error)));
+                }
+            }
+            _ => out.push_str(&format!("{}: {}\n", self.context.messages.error_prefix(), Summary(self))),
+        }
+
+        if let Some(usage) = &self.context.usage && !usage.is_empty() {
+            out.push_str(&format!("\n{}\n", usage));
+        }
+
+        out.push_str(&format!("\n{}", self.context.messages.help_hint()));
+        out
+    }
+
+    // Status a process should exit with: 0 for help and version requests, 2 for usage errors
+    pub fn exit_code(&self) -> i32 {
+        match self.kind {
+            ErrorKind::Help | ErrorKind::Version => 0,
+            _ => 2,
+        }
+    }
 }
 
 impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Summary(self))?;
+
+        if self.kind == ErrorKind::MissingArguments && let Some(usage) = &self.context.usage && !usage.is_empty() {
+            write!(f, "\n\n{}", usage)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.context.source.as_deref().map(|source| source as &(dyn Error + 'static))
+    }
+}
+
+// One line message of an error with its suggestion
+struct Summary<'a>(&'a ParseError);
+
+impl Display for Summary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.kind {
+            // Already written by the command, or made of other errors
+            ErrorKind::Help | ErrorKind::Version => write!(f, "{}", self.0.context.message.as_deref().unwrap_or_default())?,
+            ErrorKind::Multiple => write!(f, "{}", self.0.context.errors)?,
+            _ => self.0.context.messages.error(self.0, f)?,
+        }
+
+        if let Some(suggestion) = &self.0.context.suggestion {
+            self.0.context.messages.suggestion(suggestion, f)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Whether `token` is how the user typed `arg`: the argument itself, a flag with an attached value or a short in a cluster
+fn names_arg(token: &str, arg: &str) -> bool {
+    let name = token.split('=').next().unwrap_or_default();
+
+    if name == arg || (name.starts_with('-') && name.trim_start_matches('-') == arg.trim_start_matches('-')) {
+        return true;
+    }
+
+    let mut chars = arg.trim_start_matches('-').chars();
+    match (chars.next(), chars.next()) {
+        (Some(short), None) => name.starts_with('-') && !name.starts_with("--") && name[1..].contains(short),
+        _ => false,
+    }
+}
+
+// Span of `value` in `token`, narrowed to the attached part of `--arg=value` when the token has no quotes
+fn value_span(token: &Token, value: &str) -> Option<Range<usize>> {
+    if token.text == value {
+        Some(token.span.clone())
+    }
+    else if token.text.ends_with(&format!("={}", value)) {
+        match token.span.len() == token.text.len() {
+            true => Some(token.span.end - value.len()..token.span.end),
+            false => Some(token.span.clone()),
+        }
+    }
+    else {
+        None
+    }
+}
+
+// Errors gathered while parsing in the collect errors mode, see `Command::collect_errors`
+#[derive(Debug, Default)]
+pub struct ParseErrors {
+    errors: Vec<ParseError>,
+}
+
+impl ParseErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, error: ParseError) {
+        match error.kind {
+            ErrorKind::Multiple => self.errors.extend(error.context.errors.errors),
+            _ => self.errors.push(error),
+        }
+    }
+
+    // Keep the error and go on when collecting, otherwise stop on it.
+    // Help and version requests stop the parse unless the input already had errors, a missing argument is dropped
+    // in that case as the command already reported it
+    pub fn recover(&mut self, error: ParseError, collect_errors: bool) -> Result<(), ParseError> {
+        if !collect_errors {
+            return Err(error);
+        }
+
+        match error.kind {
+            ErrorKind::Help | ErrorKind::Version if self.is_empty() => Err(error),
+            ErrorKind::Help | ErrorKind::Version | ErrorKind::MissingArgument if !self.is_empty() => Ok(()),
+            _ => {
+                self.push(error);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.errors.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    pub fn iter(&self) -> core::slice::Iter<'_, ParseError> {
+        self.errors.iter()
+    }
+
+    // `value` when there is no error, the error itself when there is only one
+    pub fn into_result<T>(mut self, value: T) -> Result<T, ParseError> {
+        match self.errors.len() {
+            0 => Ok(value),
+            1 => Err(self.errors.remove(0)),
+            _ => Err(ParseError::new(ErrorKind::Multiple).errors(self)),
+        }
+    }
+}
+
+impl IntoIterator for ParseErrors {
+    type Item = ParseError;
+    type IntoIter = alloc::vec::IntoIter<ParseError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.into_iter()
+    }
+}
+
+impl Display for ParseErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, error) in self.errors.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+// Mistakes in the definition of a command, reported by `Command::validate`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DefinitionError {
+    // Command path, short, first argument, second argument
+    DuplicateShort(String, char, String, String),
+    // Command path, long, first argument, second argument
+    DuplicateLong(String, String, String, String),
+    // Command path, variadic positional, positional declared after it
+    PositionalAfterVariadic(String, String, String),
+    // Command path, argument
+    CountAndMultiple(String, String),
+    // Command path, subcommand, positional accepting its name as a value
+    SubcommandShadowsPositional(String, String, String),
+    // Command path, argument, minimum and maximum number of values
+    EmptyValueRange(String, String, usize, usize),
+    // Command path, positional declared with `index(0)`
+    ZeroIndex(String, String),
+}
+
+impl Display for DefinitionError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            ParseError::EmptyInput => write!(f, "Empty input"),
-            ParseError::Help(help) => write!(f, "{}", help),
-            ParseError::MissingArgument(arg) => write!(f, "Missing required argument: {}", arg.to_uppercase()),
-            ParseError::InvalidValue(msg) => write!(f, "Invalid value: {}", msg),
-            ParseError::UnknownArgument(arg) => write!(f, "Unknown argument: {}", arg),
-            ParseError::UnknownSubcommand => write!(f, "Unknown command"),
-            ParseError::InvalidFormat(msg) => write!(f, "Invalid format: {}", msg),
-            ParseError::UnknownEnumVariant(value, possible_values) => write!(f, "Invalid value: {}, possible values are: {}", value, possible_values),
+            DefinitionError::DuplicateShort(command, short, first, second) => write!(f, "{}: -{} is used by both {} and {}", command, short, first, second),
+            DefinitionError::DuplicateLong(command, long, first, second) => write!(f, "{}: --{} is used by both {} and {}", command, long, first, second),
+            DefinitionError::PositionalAfterVariadic(command, variadic, positional) => write!(f, "{}: the positional {} comes after the variadic positional {}", command, positional, variadic),
+            DefinitionError::CountAndMultiple(command, arg) => write!(f, "{}: {} cannot be both counted and multiple", command, arg),
+            DefinitionError::SubcommandShadowsPositional(command, subcommand, positional) => write!(f, "{}: the subcommand {} is also a value of the positional {}", command, subcommand, positional),
+            DefinitionError::EmptyValueRange(command, arg, min, max) => write!(f, "{}: {} takes an empty range of {}..={} values", command, arg, min, max),
+            DefinitionError::ZeroIndex(command, positional) => write!(f, "{}: the index of the positional {} starts at 1, not 0", command, positional),
         }
     }
-}
\ No newline at end of file
+}
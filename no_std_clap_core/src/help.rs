@@ -2,44 +2,34 @@ use core::fmt::Write;
 use alloc::format;
 use alloc::string::String;
 use alloc::vec::Vec;
+use crate::arg::arg_group::ArgGroup;
 use crate::arg::arg_info::ArgInfo;
+use crate::arg::arg_action::ArgAction;
+use crate::command::ordered_positionals;
+use crate::messages::Messages;
 use crate::subcommand::SubcommandInfo;
 
-pub fn get_help(out: &mut String, name: Option<&String>, args: &Vec<ArgInfo>, global_args: &Vec<ArgInfo>, subcommands: &Vec<SubcommandInfo>) {
+pub fn get_help(out: &mut String, messages: &dyn Messages, name: Option<&String>, args: &[ArgInfo], global_args: &[ArgInfo], groups: &[ArgGroup], subcommands: &[SubcommandInfo]) {
     if let Some(name) = name {
-        write!(out, "Usage: {}", name).unwrap();
+        get_usage(out, messages, name, args, global_args, groups, subcommands);
     }
 
     let positional_args: Vec<&ArgInfo> = args.iter().filter(|a| a.short.is_none() && a.long.is_none()).collect();
     let flag_args: Vec<&ArgInfo> = args.iter().filter(|a| a.short.is_some() || a.long.is_some()).collect();
 
-    if name.is_some() {
-        for arg in &positional_args {
-            write!(out, " <{}>", arg.name.to_uppercase()).unwrap();
-        }
-
-        if name.is_some() && (!flag_args.is_empty() || !global_args.is_empty()) {
-            write!(out, " [OPTIONS]").unwrap()
-        }
-
-        if name.is_some() && !subcommands.is_empty() {
-            write!(out, " [SUBCOMMAND]").unwrap();
-        }
-    }
-
     if name.is_some() && (!positional_args.is_empty() || !flag_args.is_empty() || !subcommands.is_empty()) {
         writeln!(out).unwrap();
         writeln!(out).unwrap();
     }
 
     if !positional_args.is_empty() {
-        writeln!(out, "Arguments:").unwrap();
+        writeln!(out, "{}:", messages.arguments()).unwrap();
         for arg in &positional_args {
             let mut line = String::new();
 
             write!(line, "{}", arg.name.to_uppercase()).unwrap();
 
-            if let Some(help) = &arg.help {
+            if let Some(help) = help_text(messages, arg) {
                 line.push_str(&format!("\t\t\t{}", help));
             }
 
@@ -52,7 +42,7 @@ pub fn get_help(out: &mut String, name: Option<&String>, args: &Vec<ArgInfo>, gl
             writeln!(out).unwrap();
         }
 
-        writeln!(out, "Options:").unwrap();
+        writeln!(out, "{}:", messages.options()).unwrap();
         for arg in &flag_args {
             let mut line = String::new();
 
@@ -64,11 +54,14 @@ pub fn get_help(out: &mut String, name: Option<&String>, args: &Vec<ArgInfo>, gl
                 }
             }
             if let Some(long) = &arg.long {
-                line.push_str("--");
+                line.push_str(match arg.negatable {
+                    true => "--[no-]",
+                    false => "--",
+                });
                 line.push_str(long);
             }
 
-            if let Some(help) = &arg.help {
+            if let Some(help) = help_text(messages, arg) {
                 line.push_str(&format!("\t\t\t{}", help));
             }
 
@@ -86,11 +79,14 @@ pub fn get_help(out: &mut String, name: Option<&String>, args: &Vec<ArgInfo>, gl
                 }
             }
             if let Some(long) = &arg.long {
-                line.push_str("--");
+                line.push_str(match arg.negatable {
+                    true => "--[no-]",
+                    false => "--",
+                });
                 line.push_str(long);
             }
 
-            if let Some(help) = &arg.help {
+            if let Some(help) = help_text(messages, arg) {
                 line.push_str(&format!("\t\t\t{}", help));
             }
 
@@ -103,7 +99,7 @@ pub fn get_help(out: &mut String, name: Option<&String>, args: &Vec<ArgInfo>, gl
             writeln!(out).unwrap();
         }
 
-        writeln!(out, "Commands:").unwrap();
+        writeln!(out, "{}:", messages.commands()).unwrap();
         for sub in subcommands {
             let mut line = sub.name.clone();
             if let Some(help) = &sub.about {
@@ -112,4 +108,93 @@ pub fn get_help(out: &mut String, name: Option<&String>, args: &Vec<ArgInfo>, gl
             writeln!(out, "  {}", line).unwrap();
         }
     }
-}
\ No newline at end of file
+}
+
+// Usage line, e.g. `Usage: myapp add <SRC> [DST] [FILES]... <--file <FILE>|--url <URL>> [OPTIONS] [SUBCOMMAND]`
+pub fn get_usage(out: &mut String, messages: &dyn Messages, name: &str, args: &[ArgInfo], global_args: &[ArgInfo], groups: &[ArgGroup], subcommands: &[SubcommandInfo]) {
+    write!(out, "{}: {}", messages.usage(), name).unwrap();
+
+    let has_flag_args = args.iter().any(|a| a.short.is_some() || a.long.is_some());
+
+    for arg in ordered_positionals(args) {
+        let arg_name = arg.name.to_uppercase();
+
+        match arg.required {
+            true => write!(out, " <{}>", arg_name).unwrap(),
+            false => write!(out, " [{}]", arg_name).unwrap(),
+        }
+
+        if arg.multiple {
+            write!(out, "...").unwrap();
+        }
+    }
+
+    // Required groups are shown as a choice between their arguments
+    for group in groups.iter().filter(|g| g.required) {
+        let choices: Vec<String> = group.args.iter()
+            .filter_map(|name| args.iter().chain(global_args.iter()).find(|a| a.name == *name))
+            .map(usage_name)
+            .collect();
+
+        write!(out, " <{}>", choices.join("|")).unwrap();
+    }
+
+    if has_flag_args || !global_args.is_empty() {
+        write!(out, " [OPTIONS]").unwrap()
+    }
+
+    if !subcommands.is_empty() {
+        write!(out, " [SUBCOMMAND]").unwrap();
+    }
+
+    for arg in args.iter().filter(|a| a.last) {
+        write!(out, " [-- <{}>...]", arg.name.to_uppercase()).unwrap();
+    }
+}
+
+// Help of an argument followed by its constraints, e.g. `Wi-Fi channel [range: 1..=14]` or `[possible values: auto, never]`.
+// The help and version flags without a help of their own get the one of the catalog
+fn help_text(messages: &dyn Messages, arg: &ArgInfo) -> Option<String> {
+    let mut text = match (&arg.help, arg.action) {
+        (Some(help), _) => help.clone(),
+        (None, ArgAction::Help) => String::from(messages.help_flag()),
+        (None, ArgAction::Version) => String::from(messages.version_flag()),
+        (None, _) => String::new(),
+    };
+
+    if let Some(range) = &arg.range {
+        if !text.is_empty() {
+            text.push(' ');
+        }
+        write!(text, "[{}: {}]", messages.range(), range).unwrap();
+    }
+
+    if !arg.possible_values.is_empty() {
+        if !text.is_empty() {
+            text.push(' ');
+        }
+        write!(text, "[{}: {}]", messages.possible_values(), arg.possible_values.join(", ")).unwrap();
+    }
+
+    match text.is_empty() {
+        true => None,
+        false => Some(text),
+    }
+}
+
+// Argument as written in usage, e.g. `--file <FILE>`
+fn usage_name(arg: &ArgInfo) -> String {
+    let value_name = format!("<{}>", arg.name.to_uppercase());
+
+    let flag = match (&arg.long, arg.short) {
+        (Some(long), _) if arg.negatable => format!("--[no-]{}", long),
+        (Some(long), _) => format!("--{}", long),
+        (None, Some(short)) => format!("-{}", short),
+        (None, None) => return value_name,
+    };
+
+    match arg.action.takes_value() {
+        true => format!("{} {}", flag, value_name),
+        false => flag,
+    }
+}
@@ -7,4 +7,7 @@ pub mod command;
 pub mod error;
 pub mod parser;
 pub mod subcommand;
-pub mod help;
\ No newline at end of file
+pub mod help;
+pub mod messages;
+pub mod suggestion;
+mod validate;
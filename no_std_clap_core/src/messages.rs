@@ -0,0 +1,186 @@
+use alloc::string::String;
+use core::fmt;
+use crate::error::{ErrorKind, ParseError};
+
+// Every text shown to the user, for the errors and the help. The provided methods are the English texts,
+// a translation overrides them and is given to `Command::messages` or `#[clap(messages = &French)]`:
+//
+// #[derive(Debug)]
+// struct French;
+//
+// impl Messages for French {
+//     fn usage(&self) -> &str { "Utilisation" }
+//     fn unknown_argument(&self, arg: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "Argument inconnu : {}", arg) }
+//     ...
+// }
+pub trait Messages: fmt::Debug + Sync {
+    // Headings of the help, "Usage" is followed by the usage line
+    fn usage(&self) -> &str {
+        "Usage"
+    }
+
+    fn arguments(&self) -> &str {
+        "Arguments"
+    }
+
+    fn options(&self) -> &str {
+        "Options"
+    }
+
+    fn commands(&self) -> &str {
+        "Commands"
+    }
+
+    // Help of the `-h/--help` and `-V/--version` flags added by the derives
+    fn help_flag(&self) -> &str {
+        "Prints help information"
+    }
+
+    fn version_flag(&self) -> &str {
+        "Prints version information"
+    }
+
+    // Constraints shown after the help of an argument, e.g. `[range: 1..=14]`
+    fn range(&self) -> &str {
+        "range"
+    }
+
+    fn possible_values(&self) -> &str {
+        "possible values"
+    }
+
+    // Start of each error line of `ParseError::render`
+    fn error_prefix(&self) -> &str {
+        "error"
+    }
+
+    // Last line of `ParseError::render`
+    fn help_hint(&self) -> &str {
+        "For more information, try '--help'"
+    }
+
+    // Message of an error, without its suggestion, written by the method of its kind below. Help and version requests
+    // and `Multiple` errors are written without them
+    fn error(&self, error: &ParseError, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let context = &error.context;
+        let arg = context.arg.as_deref().unwrap_or_default();
+        let value = context.value.as_deref().unwrap_or_default();
+        let message = context.message.as_deref().unwrap_or_default();
+        let first_expected = context.expected.first().map(String::as_str).unwrap_or_default();
+
+        match error.kind {
+            ErrorKind::EmptyInput => self.empty_input(f),
+            ErrorKind::Help | ErrorKind::Version => write!(f, "{}", message),
+            ErrorKind::MissingArgument => self.missing_argument(arg, f),
+            ErrorKind::MissingArguments => self.missing_arguments(&context.expected, f),
+            ErrorKind::MissingValue => self.missing_value(arg, f),
+            ErrorKind::TooFewValues => self.too_few_values(arg, context.min_values.unwrap_or_default(), context.num_values.unwrap_or_default(), f),
+            ErrorKind::TooManyValues => self.too_many_values(arg, context.max_values.unwrap_or_default(), f),
+            ErrorKind::InvalidValue => self.invalid_value(value, context.value_type, message, f),
+            ErrorKind::InvalidArgumentValue => self.invalid_argument_value(arg, message, f),
+            ErrorKind::OutOfRange => self.out_of_range(arg, value, first_expected, f),
+            ErrorKind::UnexpectedValue => self.unexpected_value(arg, f),
+            ErrorKind::UnknownArgument => self.unknown_argument(arg, f),
+            ErrorKind::UnknownSubcommand => self.unknown_subcommand(f),
+            ErrorKind::MissingSubcommand => self.missing_subcommand(&context.expected, f),
+            ErrorKind::GroupConflict => self.group_conflict(context.group.as_deref().unwrap_or_default(), &context.expected, f),
+            ErrorKind::ArgumentConflict => self.argument_conflict(arg, first_expected, f),
+            ErrorKind::MissingRequirement => self.missing_requirement(arg, first_expected, f),
+            ErrorKind::Ambiguous => self.ambiguous(arg, &context.expected, f),
+            ErrorKind::UnclosedQuote => self.unclosed_quote(f),
+            ErrorKind::UnknownEnumVariant => self.unknown_enum_variant(value, &context.expected, f),
+            ErrorKind::Multiple => write!(f, "{}", context.errors),
+        }
+    }
+
+    fn empty_input(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Empty input")
+    }
+
+    fn missing_argument(&self, arg: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Missing required argument: {}", arg.to_uppercase())
+    }
+
+    fn missing_arguments(&self, args: &[String], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Missing required arguments: {}", args.join(", "))
+    }
+
+    fn missing_value(&self, arg: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Missing value for argument: {}", arg)
+    }
+
+    fn too_few_values(&self, arg: &str, min: usize, given: usize, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} takes at least {} values but {} were given", arg, min, given)
+    }
+
+    fn too_many_values(&self, arg: &str, max: usize, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} takes at most {} values", arg, max)
+    }
+
+    // `value_type` is given by the built-in conversions, e.g. `u8`, the others give their `message`
+    fn invalid_value(&self, value: &str, value_type: Option<&str>, message: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match value_type {
+            Some(value_type) => write!(f, "Invalid value: Cannot parse '{}' as {}", value, value_type),
+            None => write!(f, "Invalid value: {}", message),
+        }
+    }
+
+    fn invalid_argument_value(&self, arg: &str, message: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid value for {}: {}", arg, message)
+    }
+
+    fn out_of_range(&self, arg: &str, value: &str, range: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid value: {} for {}, expected a value in {}", value, arg, range)
+    }
+
+    fn unexpected_value(&self, arg: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} does not take a value", arg)
+    }
+
+    fn unknown_argument(&self, arg: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unknown argument: {}", arg)
+    }
+
+    fn unknown_subcommand(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unknown command")
+    }
+
+    fn missing_subcommand(&self, subcommands: &[String], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Missing subcommand, expected one of: {}", subcommands.join(", "))
+    }
+
+    fn group_conflict(&self, group: &str, args: &[String], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The arguments {} cannot be used together (group {})", args.join(", "), group)
+    }
+
+    fn argument_conflict(&self, arg: &str, other: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The argument {} cannot be used with {}", arg, other)
+    }
+
+    fn missing_requirement(&self, arg: &str, required: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The argument {} requires {}", arg, required)
+    }
+
+    fn ambiguous(&self, arg: &str, candidates: &[String], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Ambiguous argument: {}, could be: {}", arg, candidates.join(", "))
+    }
+
+    fn unclosed_quote(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unclosed quote in command line")
+    }
+
+    fn unknown_enum_variant(&self, value: &str, possible_values: &[String], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid value: {}, possible values are: {}", value, possible_values.join("|"))
+    }
+
+    // Appended to the message of an error having a suggestion
+    fn suggestion(&self, suggestion: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, ", did you mean '{}'?", suggestion)
+    }
+}
+
+// Default messages
+#[derive(Debug, Clone, Copy, Default)]
+pub struct English;
+
+impl Messages for English {}
@@ -1,9 +1,13 @@
-use alloc::string::{String, ToString};
+use alloc::string::String;
 use alloc::vec::Vec;
+use core::mem;
+use core::ops::Range;
+use crate::arg::arg_group::ArgGroup;
 use crate::arg::arg_info::ArgInfo;
 use crate::arg::parsed_arg::ParsedArgs;
-use crate::error::ParseError;
+use crate::error::{ErrorKind, ParseError};
 use crate::help::get_help;
+use crate::messages::Messages;
 use crate::subcommand::SubcommandInfo;
 
 // Main parser trait
@@ -18,21 +22,21 @@ pub trait Parser: Sized {
         let args: Vec<String> = args.into_iter().map(|s| s.into()).collect();
 
         if args.is_empty() {
-            return Err(ParseError::EmptyInput);
+            return Err(ParseError::new(ErrorKind::EmptyInput));
         }
 
         Self::parse_args(&args)
     }
 
     fn parse_str(input: &str) -> Result<Self, ParseError> {
-        let input = input.trim();
-
-        if input.is_empty() {
-            return Err(ParseError::EmptyInput);
+        if input.trim().is_empty() {
+            return Err(ParseError::new(ErrorKind::EmptyInput));
         }
 
-        let args = parse_command_line(input)?;
-        Self::parse_args(&args)
+        // Errors point at the offending argument of the input, see `ParseError::render_source`
+        let tokens = parse_command_line_spans(input)?;
+        let args: Vec<String> = tokens.iter().map(|token| token.text.clone()).collect();
+        Self::parse_args(&args).map_err(|error| error.locate(&tokens))
     }
 
     fn get_help() -> String;
@@ -40,13 +44,16 @@ pub trait Parser: Sized {
 
 // Trait for types that can be used as subcommands
 pub trait Subcommand: Sized {
-    fn from_subcommand(name: &str, parents_name: Option<String>, args: &ParsedArgs) -> Result<Self, ParseError>;
+    // Shorts and longs declared by every variant, used to reject colliding global arguments at compile time
+    const FLAGS: FlagSet = FlagSet::EMPTY;
+
+    fn from_subcommand(name: &str, parents_name: Option<String>, args: &ParsedArgs, context: &ParseContext) -> Result<Self, ParseError>;
     fn subcommand_info() -> Vec<SubcommandInfo>;
-    fn get_help() -> String {
+    fn get_help(messages: &dyn Messages) -> String {
         let mut out = String::new();
         let info = Self::subcommand_info();
 
-        get_help(&mut out, None, &Vec::new(), &Vec::new(), &info);
+        get_help(&mut out, messages, None, &Vec::new(), &Vec::new(), &Vec::new(), &info);
 
         out
     }
@@ -54,22 +61,155 @@ pub trait Subcommand: Sized {
 
 // Trait for arguments that can have subcommands
 pub trait Args: Sized {
-    fn from_args(args: &ParsedArgs) -> Result<Self, ParseError>;
+    const FLAGS: FlagSet = FlagSet::EMPTY;
+
+    fn from_args(args: &ParsedArgs, context: &ParseContext) -> Result<Self, ParseError>;
     fn arg_info() -> Vec<ArgInfo>;
-    fn get_help(name: String, parents_name: Option<String>, help: Option<String>) -> String;
+    fn group_info() -> Vec<ArgGroup> {
+        Vec::new()
+    }
+    fn get_help(name: String, parents_name: Option<String>, help: Option<String>, messages: &dyn Messages) -> String;
+}
+
+// Settings and definition of the level of the command given to `Args::from_args` and `Subcommand::from_subcommand`
+#[derive(Debug, Clone, Copy)]
+pub struct ParseContext<'a> {
+    // Whether the command collects errors, the field conversions do the same
+    pub collect_errors: bool,
+    // Catalog of the command, for the help of the subcommands
+    pub messages: &'static dyn Messages,
+    // Arguments of the level and the global ones, given to `FromArg::from_arg_with` by the field conversions
+    pub args: &'a [ArgInfo],
+    pub global_args: &'a [ArgInfo],
+    pub subcommands: &'a [SubcommandInfo],
+}
+
+impl<'a> ParseContext<'a> {
+    // Argument of the level or global argument called `name`
+    pub fn arg(&self, name: &str) -> Option<&'a ArgInfo> {
+        self.args.iter().chain(self.global_args.iter()).find(|arg| arg.name == name)
+    }
+
+    // Context of the subcommand called `name`, only knowing the global arguments if there is none
+    pub fn subcommand(&self, name: &str) -> ParseContext<'a> {
+        let subcommand = self.subcommands.iter().find(|subcommand| subcommand.name == name);
+
+        ParseContext {
+            args: subcommand.map(|subcommand| subcommand.args.as_slice()).unwrap_or_default(),
+            subcommands: subcommand.map(|subcommand| subcommand.subcommands.as_slice()).unwrap_or_default(),
+            ..*self
+        }
+    }
+}
+
+// Flags declared by a command and its subcommands, built by the derives as constants.
+// Each short and long is paired with the name of the argument declaring it
+#[derive(Debug, Clone, Copy)]
+pub struct FlagSet {
+    pub shorts: &'static [(char, &'static str)],
+    pub longs: &'static [(&'static str, &'static str)],
+    pub children: &'static [&'static FlagSet],
+}
+
+impl FlagSet {
+    pub const EMPTY: FlagSet = FlagSet {
+        shorts: &[],
+        longs: &[],
+        children: &[],
+    };
+
+    // Whether an argument other than `name` uses this short, the same argument propagated down is not a collision
+    pub const fn short_collides(&self, short: char, name: &str) -> bool {
+        let mut i = 0;
+        while i < self.shorts.len() {
+            if self.shorts[i].0 == short && !const_str_eq(self.shorts[i].1, name) {
+                return true;
+            }
+            i += 1;
+        }
+
+        let mut i = 0;
+        while i < self.children.len() {
+            if self.children[i].short_collides(short, name) {
+                return true;
+            }
+            i += 1;
+        }
+
+        false
+    }
+
+    pub const fn long_collides(&self, long: &str, name: &str) -> bool {
+        let mut i = 0;
+        while i < self.longs.len() {
+            if const_str_eq(self.longs[i].0, long) && !const_str_eq(self.longs[i].1, name) {
+                return true;
+            }
+            i += 1;
+        }
+
+        let mut i = 0;
+        while i < self.children.len() {
+            if self.children[i].long_collides(long, name) {
+                return true;
+            }
+            i += 1;
+        }
+
+        false
+    }
+}
+
+const fn const_str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+
+    true
+}
+
+// Argument of a command line with its byte range in the input, quotes included
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub text: String,
+    pub span: Range<usize>,
 }
 
 // Command line string parsing function
 pub fn parse_command_line(input: &str) -> Result<Vec<String>, ParseError> {
-    let mut args = Vec::new();
+    Ok(tokenize(input, 0)?.into_iter().map(|token| token.text).collect())
+}
+
+// `parse_command_line` keeping where each argument comes from, used to point at the argument an error is about.
+// The whitespace around the line (e.g. its line ending) is ignored, the spans are still byte offsets in `input`
+pub fn parse_command_line_spans(input: &str) -> Result<Vec<Token>, ParseError> {
+    let offset = input.len() - input.trim_start().len();
+    tokenize(input.trim(), offset)
+}
+
+// Split `line` into arguments, `offset` being the position of `line` in the input
+fn tokenize(line: &str, offset: usize) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::new();
     let mut current_arg = String::new();
+    let mut start = None;
     let mut in_quotes = false;
     let mut escape_next = false;
     let mut quote_char = '"';
+    let mut quote_start = 0;
 
-    let mut chars = input.chars().peekable();
+    for (i, ch) in line.char_indices() {
+        let i = i + offset;
 
-    while let Some(ch) = chars.next() {
         if escape_next {
             current_arg.push(ch);
             escape_next = false;
@@ -77,44 +217,42 @@ pub fn parse_command_line(input: &str) -> Result<Vec<String>, ParseError> {
         }
 
         match ch {
+            ' ' | '\t' if !in_quotes => {
+                if let Some(start) = start.take() && !current_arg.is_empty() {
+                    tokens.push(Token { text: mem::take(&mut current_arg), span: start..i });
+                }
+                continue;
+            }
             '\\' if in_quotes => {
                 escape_next = true;
             }
             '"' | '\'' if !in_quotes => {
                 in_quotes = true;
                 quote_char = ch;
+                quote_start = i;
             }
             ch if ch == quote_char && in_quotes => {
                 in_quotes = false;
             }
-            ' ' | '\t' if !in_quotes => {
-                if !current_arg.is_empty() {
-                    args.push(current_arg.clone());
-                    current_arg.clear();
-                }
-                // Skip multiple whitespace
-                while chars.peek() == Some(&' ') || chars.peek() == Some(&'\t') {
-                    chars.next();
-                }
-            }
             ch => {
                 current_arg.push(ch);
             }
         }
+
+        start.get_or_insert(i);
     }
 
     if in_quotes {
-        return Err(ParseError::InvalidFormat("Unclosed quote in command line".to_string()));
+        return Err(ParseError::new(ErrorKind::UnclosedQuote).span(quote_start..offset + line.len()));
     }
 
-    if !current_arg.is_empty() {
-        args.push(current_arg);
+    if let Some(start) = start && !current_arg.is_empty() {
+        tokens.push(Token { text: current_arg, span: start..offset + line.len() });
     }
 
-    Ok(args)
+    Ok(tokens)
 }
 
-
 // Additional utility functions
 pub fn parse_env() -> Vec<String> {
     // In a real no_std environment, you'd need to provide args differently
@@ -1,9 +1,11 @@
 use alloc::format;
+use crate::arg::arg_group::ArgGroup;
 use crate::arg::arg_info::ArgInfo;
 use crate::arg::parsed_arg::ParsedArgs;
 use crate::error::ParseError;
 use crate::help::get_help;
-use crate::parser::Subcommand;
+use crate::messages::Messages;
+use crate::parser::{FlagSet, ParseContext, Subcommand};
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use core::fmt::Write;
@@ -14,6 +16,7 @@ pub struct SubcommandInfo {
     pub name: String,
     pub about: Option<String>,
     pub args: Vec<ArgInfo>,
+    pub groups: Vec<ArgGroup>,
     pub subcommands: Vec<SubcommandInfo>,
 }
 
@@ -23,6 +26,7 @@ impl SubcommandInfo {
             name: name.to_string(),
             about: None,
             args: Vec::new(),
+            groups: Vec::new(),
             subcommands: Vec::new(),
         }
     }
@@ -37,12 +41,17 @@ impl SubcommandInfo {
         self
     }
 
+    pub fn group(mut self, group: ArgGroup) -> Self {
+        self.groups.push(group);
+        self
+    }
+
     pub fn subcommand(mut self, subcommand: SubcommandInfo) -> Self {
         self.subcommands.push(subcommand);
         self
     }
 
-    pub fn get_help(&self, parents_name: Option<String>) -> String {
+    pub fn get_help(&self, parents_name: Option<String>, messages: &dyn Messages) -> String {
         let mut out = String::new();
 
         let name = match parents_name {
@@ -55,7 +64,7 @@ impl SubcommandInfo {
             writeln!(out).unwrap();
         }
 
-        get_help(&mut out, Some(&name), &self.args, &Vec::new(), &self.subcommands);
+        get_help(&mut out, messages, Some(&name), &self.args, &Vec::new(), &self.groups, &self.subcommands);
 
         out
     }
@@ -63,15 +72,17 @@ impl SubcommandInfo {
 
 // Implement Subcommand for Option<T> where T: Subcommand
 impl<T: Subcommand> Subcommand for Option<T> {
-    fn from_subcommand(name: &str, parents_name: Option<String>, args: &ParsedArgs) -> Result<Self, ParseError> {
-        T::from_subcommand(name, parents_name, args).map(Some)
+    const FLAGS: FlagSet = T::FLAGS;
+
+    fn from_subcommand(name: &str, parents_name: Option<String>, args: &ParsedArgs, context: &ParseContext) -> Result<Self, ParseError> {
+        T::from_subcommand(name, parents_name, args, context).map(Some)
     }
 
     fn subcommand_info() -> Vec<SubcommandInfo> {
         T::subcommand_info()
     }
 
-    fn get_help() -> String {
-        T::get_help()
+    fn get_help(messages: &dyn Messages) -> String {
+        T::get_help(messages)
     }
 }
\ No newline at end of file
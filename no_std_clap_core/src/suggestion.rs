@@ -0,0 +1,34 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+// Number of single character insertions, deletions or substitutions turning `a` into `b`
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = Vec::with_capacity(b.len() + 1);
+
+    for (i, a_char) in a.chars().enumerate() {
+        current.clear();
+        current.push(i + 1);
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+
+        core::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+// Closest candidate to a mistyped name, if one is close enough to be a typo: about one edit every three characters
+pub fn did_you_mean<'a>(given: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<String> {
+    let max_distance = (given.chars().count() / 3).max(1);
+
+    candidates.into_iter()
+        .map(|candidate| (levenshtein(given, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| String::from(candidate))
+}
@@ -0,0 +1,63 @@
+use crate::arg::arg_action::ArgAction;
+use crate::arg::arg_info::ArgInfo;
+use crate::command::ordered_positionals;
+use crate::error::DefinitionError;
+use crate::subcommand::SubcommandInfo;
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+// Check the arguments of a level then of its subcommands, the global arguments being known on every level.
+// Two arguments with the same name are the same argument propagated down, not a collision
+pub(crate) fn validate_level(path: &str, args: &[ArgInfo], global_args: &[ArgInfo], subcommands: &[SubcommandInfo], top_level: bool, errors: &mut Vec<DefinitionError>) {
+    let known_args: Vec<&ArgInfo> = args.iter().chain(global_args.iter()).collect();
+
+    for (i, arg_info) in known_args.iter().enumerate() {
+        // The global arguments are only checked against each other once, on the top level
+        if i >= args.len() && !top_level {
+            break;
+        }
+
+        if arg_info.count && arg_info.multiple {
+            errors.push(DefinitionError::CountAndMultiple(path.to_string(), arg_info.name.clone()));
+        }
+
+        // `num_args(3..1)` is inverted and `num_args(0..0)` accepts no value at all
+        if let Some(range) = arg_info.num_args && (range.min > range.max || range.max == 0) {
+            errors.push(DefinitionError::EmptyValueRange(path.to_string(), arg_info.name.clone(), range.min, range.max));
+        }
+
+        for other in known_args[i + 1..].iter().filter(|other| other.name != arg_info.name) {
+            if let Some(short) = arg_info.short && other.short == Some(short) {
+                errors.push(DefinitionError::DuplicateShort(path.to_string(), short, arg_info.name.clone(), other.name.clone()));
+            }
+
+            if let Some(long) = &arg_info.long && other.long.as_ref() == Some(long) {
+                errors.push(DefinitionError::DuplicateLong(path.to_string(), long.clone(), arg_info.name.clone(), other.name.clone()));
+            }
+        }
+    }
+
+    let positionals = ordered_positionals(args);
+
+    for positional in positionals.iter().filter(|a| a.index == Some(0)) {
+        errors.push(DefinitionError::ZeroIndex(path.to_string(), positional.name.clone()));
+    }
+
+    if let Some(variadic) = positionals.iter().position(|a| a.action == ArgAction::Append) {
+        for positional in &positionals[variadic + 1..] {
+            errors.push(DefinitionError::PositionalAfterVariadic(path.to_string(), positionals[variadic].name.clone(), positional.name.clone()));
+        }
+    }
+
+    for subcommand in subcommands {
+        for positional in positionals.iter().filter(|a| a.possible_values.contains(&subcommand.name)) {
+            errors.push(DefinitionError::SubcommandShadowsPositional(path.to_string(), subcommand.name.clone(), positional.name.clone()));
+        }
+    }
+
+    for subcommand in subcommands {
+        let sub_path = format!("{} {}", path, subcommand.name);
+        validate_level(&sub_path, &subcommand.args, global_args, &subcommand.subcommands, false, errors);
+    }
+}
@@ -1,9 +1,10 @@
 use proc_macro::TokenStream;
-use proc_macro2::Ident;
 use quote::quote;
-use syn::{Data, DeriveInput, Error, Fields, FieldsNamed};
-use crate::field::{generate_field_assignments, generate_field_parsers, parse_field_attributes};
-use crate::utils::{get_inner_type, is_bool_type, is_option_type, is_vec_type};
+use syn::{Data, DeriveInput, Error, Expr, Field, Fields, FieldsNamed, Type};
+use crate::collision::{check_flag_collisions, generate_flag_set, HELP_FLAG};
+use crate::field::{generate_field_assignments, generate_field_conversions, generate_field_parsers, is_required, parse_field_attributes, FieldAttributes};
+use crate::group::generate_group_definitions;
+use crate::utils::{get_array_type, get_value_type, is_bool_type, is_number_type, is_option_bool_type, is_vec_type, to_kebab_case_case};
 
 pub fn derive_args_impl(input: DeriveInput) -> Result<TokenStream, Error> {
     let name = &input.ident;
@@ -12,17 +13,29 @@ pub fn derive_args_impl(input: DeriveInput) -> Result<TokenStream, Error> {
         Data::Struct(data_struct) => {
             match data_struct.fields {
                 Fields::Named(fields) => {
+                    // Subcommands get their own `-h/--help`
+                    check_flag_collisions(&fields, &[HELP_FLAG])?;
+
+                    let flag_set = generate_flag_set(&fields)?;
                     let field_parsers = generate_field_parsers(&fields)?;
+                    let field_conversions = generate_field_conversions(&fields)?;
                     let field_assignments = generate_field_assignments(&fields)?;
                     let arg_info_generation = generate_arg_info_for_args(&fields)?;
+                    let group_definitions = generate_group_definitions(&input.attrs, &fields, &to_kebab_case_case(name.to_string()))?;
 
                     let expanded = quote! {
                         impl ::no_std_clap_core::parser::Args for #name {
-                            fn from_args(parsed: &::no_std_clap_core::arg::parsed_arg::ParsedArgs) -> ::core::result::Result<Self, ::no_std_clap_core::error::ParseError> {
+                            const FLAGS: ::no_std_clap_core::parser::FlagSet = #flag_set;
+
+                            fn from_args(parsed: &::no_std_clap_core::arg::parsed_arg::ParsedArgs, context: &::no_std_clap_core::parser::ParseContext) -> ::core::result::Result<Self, ::no_std_clap_core::error::ParseError> {
                                 use ::no_std_clap_core::arg::from_arg::FromArg;
                                 use ::alloc::string::ToString;
 
+                                let mut parse_errors = ::no_std_clap_core::error::ParseErrors::new();
+
                                 #(#field_parsers)*
+                                #(#field_conversions)*
+                                parse_errors.into_result(())?;
 
                                 Ok(Self {
                                     #(#field_assignments)*
@@ -37,10 +50,17 @@ pub fn derive_args_impl(input: DeriveInput) -> Result<TokenStream, Error> {
                                 ]
                             }
 
-                            fn get_help(name: ::alloc::string::String, parents_name: Option<::alloc::string::String>, help: Option<::alloc::string::String>) -> ::alloc::string::String {
+                            fn group_info() -> ::alloc::vec::Vec<::no_std_clap_core::arg::arg_group::ArgGroup> {
+                                ::alloc::vec![
+                                    #(#group_definitions,)*
+                                ]
+                            }
+
+                            fn get_help(name: ::alloc::string::String, parents_name: Option<::alloc::string::String>, help: Option<::alloc::string::String>, messages: &dyn ::no_std_clap_core::messages::Messages) -> ::alloc::string::String {
                                 use core::fmt::Write;
                                 let mut out = ::alloc::string::String::new();
                                 let arg_infos = Self::arg_info();
+                                let group_infos = Self::group_info();
 
                                 if let Some(help) = help {
                                     writeln!(out, "{}", help).unwrap();
@@ -52,7 +72,7 @@ pub fn derive_args_impl(input: DeriveInput) -> Result<TokenStream, Error> {
                                     None => name,
                                 };
 
-                                ::no_std_clap_core::help::get_help(&mut out, Some(&name), &arg_infos, &::alloc::vec::Vec::new(), &::alloc::vec::Vec::new());
+                                ::no_std_clap_core::help::get_help(&mut out, messages, Some(&name), &arg_infos, &::alloc::vec::Vec::new(), &group_infos, &::alloc::vec::Vec::new());
 
                                 out
                             }
@@ -75,50 +95,13 @@ pub fn generate_arg_definitions(fields: &FieldsNamed) -> Result<Vec<proc_macro2:
     let mut definitions = Vec::new();
 
     for field in &fields.named {
-        let field_name = field.ident.as_ref().unwrap();
         let field_attrs = parse_field_attributes(field)?;
 
-        if field_attrs.skip {
+        if field_attrs.skip || field_attrs.subcommand {
             continue;
         }
 
-        let field_name_str = field_name.to_string();
-        let is_vec = is_vec_type(&field.ty);
-
-        let mut arg_info_def = quote! {
-            ArgInfo::new(#field_name_str)
-        };
-
-        if let Some(short) = field_attrs.short {
-            let short_str = short.to_string();
-            arg_info_def.extend(quote! {
-                .short(#short_str.chars().next().unwrap())
-            });
-        }
-
-        if let Some(long) = &field_attrs.long {
-            arg_info_def.extend(quote! {
-                .long(#long)
-            });
-        }
-
-        if let Some(help) = &field_attrs.help {
-            arg_info_def.extend(quote! {
-                .help(#help)
-            });
-        }
-
-        if field_attrs.required {
-            arg_info_def.extend(quote! {
-                .required()
-            });
-        }
-
-        if field_attrs.multiple || is_vec {
-            arg_info_def.extend(quote! {
-                .multiple()
-            });
-        }
+        let mut arg_info_def = generate_arg_info(field, &field_attrs);
 
         if field_attrs.global {
             arg_info_def.extend(quote! {
@@ -126,12 +109,6 @@ pub fn generate_arg_definitions(fields: &FieldsNamed) -> Result<Vec<proc_macro2:
             });
         }
 
-        if field_attrs.count {
-            arg_info_def.extend(quote! {
-                .count()
-            });
-        }
-
         definitions.push(arg_info_def);
     }
 
@@ -142,56 +119,13 @@ pub fn generate_global_arg_definitions(fields: &FieldsNamed) -> Result<Vec<proc_
     let mut definitions = Vec::new();
 
     for field in &fields.named {
-        let field_name = field.ident.as_ref().unwrap();
         let field_attrs = parse_field_attributes(field)?;
 
         if field_attrs.skip || !field_attrs.global {
             continue;
         }
 
-        let field_name_str = field_name.to_string();
-        let is_vec = is_vec_type(&field.ty);
-
-        let mut arg_info_def = quote! {
-            ArgInfo::new(#field_name_str)
-        };
-
-        if let Some(short) = field_attrs.short {
-            let short_str = short.to_string();
-            arg_info_def.extend(quote! {
-                .short(#short_str.chars().next().unwrap())
-            });
-        }
-
-        if let Some(long) = &field_attrs.long {
-            arg_info_def.extend(quote! {
-                .long(#long)
-            });
-        }
-
-        if let Some(help) = &field_attrs.help {
-            arg_info_def.extend(quote! {
-                .help(#help)
-            });
-        }
-
-        if field_attrs.required {
-            arg_info_def.extend(quote! {
-                .required()
-            });
-        }
-
-        if field_attrs.multiple || is_vec {
-            arg_info_def.extend(quote! {
-                .multiple()
-            });
-        }
-
-        if field_attrs.count {
-            arg_info_def.extend(quote! {
-                .count()
-            });
-        }
+        let mut arg_info_def = generate_arg_info(field, &field_attrs);
 
         // ensure .global() present for clarity (should be true here)
         arg_info_def.extend(quote! {
@@ -210,201 +144,180 @@ pub fn generate_arg_info_for_args(fields: &FieldsNamed) -> Result<Vec<proc_macro
     let mut arg_infos = Vec::new();
 
     for field in &fields.named {
-        let field_name = field.ident.as_ref().unwrap();
         let field_attrs = parse_field_attributes(field)?;
 
         if field_attrs.skip || field_attrs.subcommand {
             continue;
         }
 
-        let field_name_str = field_name.to_string();
-        let is_vec = is_vec_type(&field.ty);
+        let mut arg_info_def = generate_arg_info(field, &field_attrs);
 
-        let mut arg_info_def = quote! {
-            ArgInfo::new(#field_name_str)
-        };
+        arg_info_def.extend(quote! { , });
 
-        if let Some(short) = field_attrs.short {
-            let short_str = short.to_string();
-            arg_info_def.extend(quote! {
-                .short(#short_str.chars().next().unwrap())
-            });
-        }
+        arg_infos.push(arg_info_def);
+    }
 
-        if let Some(long) = &field_attrs.long {
-            arg_info_def.extend(quote! {
-                .long(#long)
-            });
-        }
+    Ok(arg_infos)
+}
 
-        if let Some(help) = &field_attrs.help {
-            arg_info_def.extend(quote! {
-                .help(#help)
-            });
-        }
+// Build the ArgInfo expression shared by every derive, without the `.global()` marker
+pub fn generate_arg_info(field: &Field, field_attrs: &FieldAttributes) -> proc_macro2::TokenStream {
+    let field_name_str = field.ident.as_ref().unwrap().to_string();
+    let is_vec = is_vec_type(&field.ty);
 
-        if field_attrs.required {
-            arg_info_def.extend(quote! {
-                .required()
-            });
-        }
+    let mut arg_info_def = quote! {
+        ::no_std_clap_core::arg::arg_info::ArgInfo::new(#field_name_str)
+    };
 
-        if field_attrs.multiple || is_vec {
-            arg_info_def.extend(quote! {
-                .multiple()
-            });
-        }
+    if let Some(short) = field_attrs.short {
+        let short_str = short.to_string();
+        arg_info_def.extend(quote! {
+            .short(#short_str.chars().next().unwrap())
+        });
+    }
 
-        if field_attrs.count {
-            arg_info_def.extend(quote! {
-                .count()
-            });
-        }
+    if let Some(long) = &field_attrs.long {
+        arg_info_def.extend(quote! {
+            .long(#long)
+        });
+    }
 
-        arg_info_def.extend(quote! { , });
+    if let Some(help) = &field_attrs.help {
+        arg_info_def.extend(quote! {
+            .help(#help)
+        });
+    }
 
-        arg_infos.push(arg_info_def);
+    if is_required(field, field_attrs) {
+        arg_info_def.extend(quote! {
+            .required()
+        });
     }
 
-    Ok(arg_infos)
-}
+    if field_attrs.multiple || is_vec {
+        arg_info_def.extend(quote! {
+            .multiple()
+        });
+    }
 
-// Helper function for generating field parsers in Args context
-pub fn generate_args_field_parsers(fields: &FieldsNamed) -> Result<Vec<proc_macro2::TokenStream>, Error> {
-    let mut parsers = Vec::new();
+    // An array takes exactly its length in values
+    if let Some(num_args) = &field_attrs.num_args {
+        arg_info_def.extend(quote! {
+            .num_args(#num_args)
+        });
+    }
+    else if let Some((_, len)) = get_array_type(&field.ty) {
+        arg_info_def.extend(quote! {
+            .num_args(#len)
+        });
+    }
 
-    for field in &fields.named {
-        let field_name = field.ident.as_ref().unwrap();
-        let field_attrs = parse_field_attributes(field)?;
+    if !field_attrs.possible_values.is_empty() {
+        let possible_values = &field_attrs.possible_values;
+        arg_info_def.extend(quote! {
+            .possible_values(&[#(#possible_values),*])
+        });
+    }
 
-        if field_attrs.skip || field_attrs.subcommand {
-            continue;
-        }
+    if let Some(range) = &field_attrs.range {
+        let range = typed_range(range, get_value_type(&field.ty));
+        arg_info_def.extend(quote! {
+            .range(#range)
+        });
+    }
+
+    if field_attrs.count {
+        arg_info_def.extend(quote! {
+            .count()
+        });
+    }
 
-        let field_name_str = field_name.to_string();
-        let var_name = Ident::new(&format!("parsed_{}", field_name), field_name.span());
+    if field_attrs.last {
+        arg_info_def.extend(quote! {
+            .last()
+        });
+    }
 
-        let is_optional = is_option_type(&field.ty);
-        let is_vec = is_vec_type(&field.ty);
-        let is_bool = is_bool_type(&field.ty);
+    for name in &field_attrs.conflicts_with {
+        arg_info_def.extend(quote! {
+            .conflicts_with(#name)
+        });
+    }
 
-        let parser = if field_attrs.count {
-            quote! {
-                let #var_name = args.count(#field_name_str);
-            }
-        }
-        else if is_bool {
-            quote! {
-                let #var_name = args.contains_key(#field_name_str);
-            }
-        }
-        else if is_vec {
-            quote! {
-                let #var_name = args.get_all(#field_name_str);
-            }
-        }
-        else if field_attrs.required && !is_optional {
-            quote! {
-                let #var_name = args.get(#field_name_str)
-                    .ok_or_else(|| ::no_std_clap_core::error::ParseError::MissingArgument(#field_name_str.to_string()))?;
-            }
-        }
-        else if let Some(default) = &field_attrs.default_value {
-            quote! {
-                let #var_name = args.get(#field_name_str)
-                    .map(|s| Some(s.as_str()))
-                    .unwrap_or(Some(#default));
-            }
-        }
-        else {
-            quote! {
-                let #var_name = args.get(#field_name_str).map(|s| s.as_str());
-            }
-        };
+    for name in &field_attrs.requires {
+        arg_info_def.extend(quote! {
+            .requires(#name)
+        });
+    }
 
-        parsers.push(parser);
+    for name in &field_attrs.overrides_with {
+        arg_info_def.extend(quote! {
+            .overrides_with(#name)
+        });
     }
 
-    Ok(parsers)
-}
+    if field_attrs.negatable {
+        arg_info_def.extend(quote! {
+            .negatable()
+        });
+    }
 
-// Helper function for generating field assignments in Args context
-pub fn generate_args_field_assignments(fields: &FieldsNamed) -> Result<Vec<proc_macro2::TokenStream>, Error> {
-    let mut assignments = Vec::new();
+    for (name, value) in &field_attrs.required_if_eq {
+        arg_info_def.extend(quote! {
+            .required_if_eq(#name, #value)
+        });
+    }
 
-    for field in &fields.named {
-        let field_name = field.ident.as_ref().unwrap();
-        let field_attrs = parse_field_attributes(field)?;
+    for name in &field_attrs.required_unless_present {
+        arg_info_def.extend(quote! {
+            .required_unless_present(#name)
+        });
+    }
 
-        if field_attrs.skip || field_attrs.subcommand {
-            assignments.push(quote! {
-                #field_name: ::core::default::Default::default()
-            });
-            continue;
-        }
+    for (name, value, default) in &field_attrs.default_value_if {
+        arg_info_def.extend(quote! {
+            .default_value_if(#name, #value, #default)
+        });
+    }
 
-        let var_name = Ident::new(&format!("parsed_{}", field_name), field_name.span());
-        let field_type = &field.ty;
+    if let Some(index) = &field_attrs.index {
+        arg_info_def.extend(quote! {
+            .index(#index)
+        });
+    }
 
-        let is_optional = is_option_type(field_type);
-        let is_vec = is_vec_type(field_type);
-        let is_bool = is_bool_type(field_type);
-        let is_count = field_attrs.count;
+    if field_attrs.allow_hyphen_values {
+        arg_info_def.extend(quote! {
+            .allow_hyphen_values()
+        });
+    }
 
-        let assignment = if is_bool {
-            quote! { #field_name: #var_name }
-        }
-        else if is_count {
-            quote! {
-                #field_name: #var_name.count()
-            }
-        }
-        else if is_vec {
-            let inner_type = get_inner_type(field_type).unwrap_or(field_type);
-            quote! {
-                #field_name: {
-                    let mut vec = ::alloc::vec::Vec::new();
-                    for value in #var_name {
-                        vec.push(<#inner_type as ::no_std_clap_core::arg::from_arg::FromArg>::from_arg(value)?);
-                    }
-                    vec
-                }
-            }
-        }
-        else if is_optional {
-            let inner_type = get_inner_type(field_type).unwrap_or(field_type);
-            quote! {
-                #field_name: match #var_name {
-                    Some(s) => Some(<#inner_type as ::no_std_clap_core::arg::from_arg::FromArg>::from_arg(s)?),
-                    None => None,
-                }
-            }
-        }
-        else if field_attrs.required || field_attrs.default_value.is_some() {
-            quote! {
-                #field_name: <#field_type as ::no_std_clap_core::arg::from_arg::FromArg>::from_arg(#var_name)?
-            }
-        }
-        else if is_optional {
-            let inner_type = get_inner_type(field_type).unwrap_or(field_type);
-            quote! {
-                #field_name: match #var_name {
-                    Some(s) => Some(<#inner_type as FromArg>::from_arg(s)?),
-                    None => None,
-                }
-            }
-        }
-        else {
-            quote! {
-                #field_name: match #var_name {
-                    Some(s) => <#field_type as ::no_std_clap_core::arg::from_arg::FromArg>::from_arg(s)?,
-                    None => ::core::default::Default::default(),
-                }
-            }
-        };
+    // Explicit action last so it wins over the inferred one
+    if let Some(action) = &field_attrs.action {
+        arg_info_def.extend(quote! {
+            .action(::no_std_clap_core::arg::arg_action::ArgAction::#action)
+        });
+    }
+    else if is_bool_type(&field.ty) || is_option_bool_type(&field.ty) {
+        arg_info_def.extend(quote! {
+            .action(::no_std_clap_core::arg::arg_action::ArgAction::SetTrue)
+        });
+    }
+
+    arg_info_def
+}
+
+// Bounds of `range = ...` cast to the number type of the field, so that `0..=10` bounds a `f64` and
+// `..=5_000_000_000` a `u64`
+fn typed_range(range: &Expr, value_type: &Type) -> proc_macro2::TokenStream {
+    if let Expr::Range(expr_range) = range
+        && is_number_type(value_type) {
+        let start = expr_range.start.as_ref().map(|start| quote! { (#start as #value_type) });
+        let end = expr_range.end.as_ref().map(|end| quote! { (#end as #value_type) });
+        let limits = &expr_range.limits;
 
-        assignments.push(assignment);
+        return quote! { #start #limits #end };
     }
 
-    Ok(assignments)
+    quote! { #range }
 }
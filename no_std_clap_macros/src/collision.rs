@@ -0,0 +1,144 @@
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{DataEnum, Error, FieldsNamed};
+use crate::field::parse_field_attributes;
+use crate::utils::get_inner_type;
+
+// Flags added by the derives themselves
+pub const HELP_FLAG: (char, &str) = ('h', "help");
+pub const VERSION_FLAG: (char, &str) = ('V', "version");
+
+// Reject two fields (or a field and a reserved flag) sharing a short or a long
+pub fn check_flag_collisions(fields: &FieldsNamed, reserved: &[(char, &str)]) -> Result<(), Error> {
+    let mut shorts: Vec<(char, String)> = reserved.iter().map(|(short, name)| (*short, name.to_string())).collect();
+    let mut longs: Vec<(String, String)> = reserved.iter().map(|(_, name)| (name.to_string(), name.to_string())).collect();
+
+    for field in &fields.named {
+        let field_attrs = parse_field_attributes(field)?;
+
+        if field_attrs.skip || field_attrs.subcommand {
+            continue;
+        }
+
+        let field_name = field.ident.as_ref().unwrap().to_string();
+
+        if let Some(short) = field_attrs.short {
+            if let Some((_, other)) = shorts.iter().find(|(s, _)| *s == short) {
+                return Err(Error::new_spanned(field, format!("short `-{}` of `{}` is already used by `{}`", short, field_name, other)));
+            }
+
+            shorts.push((short, field_name.clone()));
+        }
+
+        for long in field_longs(&field_attrs.long, field_attrs.negatable) {
+            if let Some((_, other)) = longs.iter().find(|(l, _)| *l == long) {
+                return Err(Error::new_spanned(field, format!("long `--{}` of `{}` is already used by `{}`", long, field_name, other)));
+            }
+
+            longs.push((long, field_name.clone()));
+        }
+    }
+
+    Ok(())
+}
+
+// Reject two variants resolving to the same subcommand name
+pub fn check_subcommand_collisions(data_enum: &DataEnum, names: &[String]) -> Result<(), Error> {
+    for (i, (variant, name)) in data_enum.variants.iter().zip(names).enumerate() {
+        if let Some(other) = names[..i].iter().position(|other| other == name) {
+            let other = &data_enum.variants[other].ident;
+            return Err(Error::new_spanned(variant, format!("subcommand name `{}` of `{}` is already used by `{}`", name, variant.ident, other)));
+        }
+    }
+
+    Ok(())
+}
+
+// `FlagSet` constant describing the flags of a struct (and of its subcommands)
+pub fn generate_flag_set(fields: &FieldsNamed) -> Result<proc_macro2::TokenStream, Error> {
+    let mut shorts = Vec::new();
+    let mut longs = Vec::new();
+    let mut children = Vec::new();
+
+    for field in &fields.named {
+        let field_attrs = parse_field_attributes(field)?;
+
+        if field_attrs.skip {
+            continue;
+        }
+
+        if field_attrs.subcommand {
+            let subcommand_type = get_inner_type(&field.ty).unwrap_or(&field.ty);
+            children.push(quote! { &<#subcommand_type as ::no_std_clap_core::parser::Subcommand>::FLAGS });
+            continue;
+        }
+
+        let field_name = field.ident.as_ref().unwrap().to_string();
+
+        if let Some(short) = field_attrs.short {
+            shorts.push(quote! { (#short, #field_name) });
+        }
+
+        for long in field_longs(&field_attrs.long, field_attrs.negatable) {
+            longs.push(quote! { (#long, #field_name) });
+        }
+    }
+
+    Ok(quote! {
+        ::no_std_clap_core::parser::FlagSet {
+            shorts: &[#(#shorts),*],
+            longs: &[#(#longs),*],
+            children: &[#(#children),*],
+        }
+    })
+}
+
+// Const assertions rejecting global arguments whose flags are used by another argument of a subcommand
+pub fn generate_global_collision_assertions(fields: &FieldsNamed) -> Result<Vec<proc_macro2::TokenStream>, Error> {
+    let mut subcommand_types = Vec::new();
+    let mut globals = Vec::new();
+
+    for field in &fields.named {
+        let field_attrs = parse_field_attributes(field)?;
+
+        if field_attrs.subcommand {
+            subcommand_types.push(get_inner_type(&field.ty).unwrap_or(&field.ty).clone());
+        }
+        else if field_attrs.global && !field_attrs.skip {
+            globals.push((field, field_attrs));
+        }
+    }
+
+    let mut assertions = Vec::new();
+
+    for subcommand_type in &subcommand_types {
+        for (field, field_attrs) in &globals {
+            let field_name = field.ident.as_ref().unwrap().to_string();
+
+            if let Some(short) = field_attrs.short {
+                let message = format!("global short `-{}` of `{}` is already used by a subcommand argument", short, field_name);
+                assertions.push(quote_spanned! { field.span() =>
+                    const _: () = ::core::assert!(!<#subcommand_type as ::no_std_clap_core::parser::Subcommand>::FLAGS.short_collides(#short, #field_name), #message);
+                });
+            }
+
+            for long in field_longs(&field_attrs.long, field_attrs.negatable) {
+                let message = format!("global long `--{}` of `{}` is already used by a subcommand argument", long, field_name);
+                assertions.push(quote_spanned! { field.span() =>
+                    const _: () = ::core::assert!(!<#subcommand_type as ::no_std_clap_core::parser::Subcommand>::FLAGS.long_collides(#long, #field_name), #message);
+                });
+            }
+        }
+    }
+
+    Ok(assertions)
+}
+
+// The long and, for negatable flags, its `no-` form
+fn field_longs(long: &Option<String>, negatable: bool) -> Vec<String> {
+    match long {
+        Some(long) if negatable => vec![long.clone(), format!("no-{}", long)],
+        Some(long) => vec![long.clone()],
+        None => Vec::new(),
+    }
+}
@@ -20,22 +20,22 @@ pub fn derive_enum_values_arg_impl(input: DeriveInput) -> Result<TokenStream, Er
 
         // look for #[arg(name = "...")]
         for attr in &variant.attrs {
-            if attr.path().is_ident("arg") {
-                match &attr.meta {
-                    Meta::List(_meta_list) => {
-                        attr.parse_nested_meta(|meta| {
-                            if meta.path.is_ident("name") {
-                                let value: LitStr = meta.value()?.parse()?;
-                                variant_name = value.value().to_string();
-                            }
-                            Ok(())
-                        })?;
-                    },
-                    _ => {}
-                }
+            if attr.path().is_ident("arg") && let Meta::List(_) = &attr.meta {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("name") {
+                        let value: LitStr = meta.value()?.parse()?;
+                        variant_name = value.value().to_string();
+                    }
+                    Ok(())
+                })?;
             }
         }
 
+        if let Some(index) = variant_names.iter().position(|name| *name == variant_name) {
+            let other = &variants[index].ident;
+            return Err(Error::new_spanned(variant, format!("value `{}` of `{}` is already used by `{}`", variant_name, v_ident, other)));
+        }
+
         variant_names.push(variant_name.clone());
 
         arms.push(quote! {
@@ -43,14 +43,15 @@ pub fn derive_enum_values_arg_impl(input: DeriveInput) -> Result<TokenStream, Er
         });
     }
 
-    let variant_name_stringed = variant_names.join("|");
-
     let expanded = quote! {
         impl ::no_std_clap_core::arg::from_arg::FromArg for #name {
             fn from_arg(value: &str) -> Result<Self, ::no_std_clap_core::error::ParseError> {
                 match value {
                     #(#arms)*
-                    other => Err(::no_std_clap_core::error::ParseError::UnknownEnumVariant(::alloc::string::String::from(other), ::alloc::string::String::from(#variant_name_stringed))),
+                    other => Err(::no_std_clap_core::error::ParseError::new(::no_std_clap_core::error::ErrorKind::UnknownEnumVariant)
+                        .value(other)
+                        .expected(::alloc::vec![#(::alloc::string::String::from(#variant_names)),*])
+                        .suggestion(::no_std_clap_core::suggestion::did_you_mean(other, [#(#variant_names),*]))),
                 }
             }
         }
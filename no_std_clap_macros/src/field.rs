@@ -1,6 +1,11 @@
-use crate::utils::{get_inner_type, is_bool_type, is_option_type, is_vec_type};
+use crate::utils::{append_doc_comment, get_array_type, get_inner_type, is_bool_type, is_option_bool_type, is_option_type, is_vec_type, is_vec_vec_type};
 use quote::{format_ident, quote};
-use syn::{Error, Expr, Field, FieldsNamed, Lit, LitStr, Meta};
+use proc_macro2::Ident;
+use syn::{parenthesized, Error, Expr, ExprArray, ExprLit, Field, FieldsNamed, Lit, LitInt, LitStr, Meta, Path, Token, Type};
+
+const ARG_ACTIONS: [&str; 7] = ["SetTrue", "SetFalse", "Set", "Append", "Count", "Help", "Version"];
+// Actions which take no value, their fields always have one
+const FLAG_ACTIONS: [&str; 5] = ["SetTrue", "SetFalse", "Count", "Help", "Version"];
 
 #[derive(Default)]
 pub struct FieldAttributes {
@@ -13,7 +18,23 @@ pub struct FieldAttributes {
     pub skip: bool,
     pub subcommand: bool,
     pub global: bool,
-    pub count: bool
+    pub count: bool,
+    pub last: bool,
+    pub allow_hyphen_values: bool,
+    pub action: Option<Ident>,
+    pub num_args: Option<Expr>,
+    pub value_parser: Option<Path>,
+    pub range: Option<Expr>,
+    pub possible_values: Vec<String>,
+    pub index: Option<LitInt>,
+    pub group: Option<String>,
+    pub conflicts_with: Vec<String>,
+    pub requires: Vec<String>,
+    pub overrides_with: Vec<String>,
+    pub negatable: bool,
+    pub required_if_eq: Vec<(String, String)>,
+    pub required_unless_present: Vec<String>,
+    pub default_value_if: Vec<(String, String, String)>,
 }
 
 pub fn parse_field_attributes(field: &Field) -> Result<FieldAttributes, Error> {
@@ -26,13 +47,11 @@ pub fn parse_field_attributes(field: &Field) -> Result<FieldAttributes, Error> {
                     attr.parse_nested_meta(|meta| {
                         if meta.path.is_ident("short") {
                             if let Ok(value) = meta.value() {
-                                let lit: LitStr = value.parse()?;
-                                let s = lit.value();
-                                if s.len() == 1 {
-                                    field_attrs.short = s.chars().next();
-                                }
-                                else {
-                                    return Err(meta.error("short must be a single character"));
+                                // Either `short = 'n'` or `short = "n"`
+                                match value.parse()? {
+                                    Lit::Char(lit) => field_attrs.short = Some(lit.value()),
+                                    Lit::Str(lit) if lit.value().chars().count() == 1 => field_attrs.short = lit.value().chars().next(),
+                                    _ => return Err(meta.error("short must be a single character")),
                                 }
                             }
                             else {
@@ -77,6 +96,104 @@ pub fn parse_field_attributes(field: &Field) -> Result<FieldAttributes, Error> {
                         else if meta.path.is_ident("count") {
                             field_attrs.count = true;
                         }
+                        else if meta.path.is_ident("last") {
+                            field_attrs.last = true;
+                        }
+                        else if meta.path.is_ident("allow_hyphen_values") {
+                            field_attrs.allow_hyphen_values = true;
+                        }
+                        else if meta.path.is_ident("action") {
+                            // Either `action = SetFalse` or `action = ArgAction::SetFalse`
+                            let value: Path = meta.value()?.parse()?;
+                            let action = value.segments.last().unwrap().ident.clone();
+
+                            if !ARG_ACTIONS.iter().any(|known| action == known) {
+                                return Err(Error::new_spanned(value, format!("unknown action, expected one of: {}", ARG_ACTIONS.join(", "))));
+                            }
+
+                            // Both spellings of a counter are read with `parsed.count`
+                            if action == "Count" {
+                                field_attrs.count = true;
+                            }
+
+                            field_attrs.action = Some(action);
+                        }
+                        else if meta.path.is_ident("num_args") {
+                            // Either `num_args = 2` or a range like `num_args = 1..=3`
+                            let value: Expr = meta.value()?.parse()?;
+                            field_attrs.num_args = Some(value);
+                        }
+                        else if meta.path.is_ident("group") {
+                            let value: LitStr = meta.value()?.parse()?;
+                            field_attrs.group = Some(value.value());
+                        }
+                        else if meta.path.is_ident("conflicts_with") {
+                            let value: LitStr = meta.value()?.parse()?;
+                            field_attrs.conflicts_with.push(value.value());
+                        }
+                        else if meta.path.is_ident("requires") {
+                            let value: LitStr = meta.value()?.parse()?;
+                            field_attrs.requires.push(value.value());
+                        }
+                        else if meta.path.is_ident("overrides_with") {
+                            let value: LitStr = meta.value()?.parse()?;
+                            field_attrs.overrides_with.push(value.value());
+                        }
+                        else if meta.path.is_ident("negatable") {
+                            field_attrs.negatable = true;
+                        }
+                        else if meta.path.is_ident("required_if_eq") {
+                            // `required_if_eq("mode", "raw")`
+                            let content;
+                            parenthesized!(content in meta.input);
+                            let name: LitStr = content.parse()?;
+                            content.parse::<Token![,]>()?;
+                            let value: LitStr = content.parse()?;
+                            field_attrs.required_if_eq.push((name.value(), value.value()));
+                        }
+                        else if meta.path.is_ident("required_unless_present") {
+                            let value: LitStr = meta.value()?.parse()?;
+                            field_attrs.required_unless_present.push(value.value());
+                        }
+                        else if meta.path.is_ident("default_value_if") {
+                            // `default_value_if("mode", "fast", "8")`
+                            let content;
+                            parenthesized!(content in meta.input);
+                            let name: LitStr = content.parse()?;
+                            content.parse::<Token![,]>()?;
+                            let value: LitStr = content.parse()?;
+                            content.parse::<Token![,]>()?;
+                            let default: LitStr = content.parse()?;
+                            field_attrs.default_value_if.push((name.value(), value.value(), default.value()));
+                        }
+                        else if meta.path.is_ident("value_parser") {
+                            // Any `fn(&str) -> Result<T, E>` where `E` converts into a `Box<dyn Error + Send + Sync>`
+                            let value: Path = meta.value()?.parse()?;
+                            field_attrs.value_parser = Some(value);
+                        }
+                        else if meta.path.is_ident("possible_values") {
+                            // `possible_values = ["auto", "always", "never"]`
+                            let value: ExprArray = meta.value()?.parse()?;
+                            for element in &value.elems {
+                                let Expr::Lit(ExprLit { lit: Lit::Str(lit_str), .. }) = element
+                                else {
+                                    return Err(Error::new_spanned(element, "possible values must be string literals"));
+                                };
+                                field_attrs.possible_values.push(lit_str.value());
+                            }
+                        }
+                        else if meta.path.is_ident("range") {
+                            // Any range expression, e.g. `range = 1..=14` or `range = 0.5..`
+                            let value: Expr = meta.value()?.parse()?;
+                            if !matches!(value, Expr::Range(_)) {
+                                return Err(Error::new_spanned(value, "range must be a range expression such as 1..=14"));
+                            }
+                            field_attrs.range = Some(value);
+                        }
+                        else if meta.path.is_ident("index") {
+                            let value: LitInt = meta.value()?.parse()?;
+                            field_attrs.index = Some(value);
+                        }
                         Ok(())
                     })?;
                 }
@@ -108,28 +225,9 @@ pub fn parse_field_attributes(field: &Field) -> Result<FieldAttributes, Error> {
             }
         }
         else if attr.path().is_ident("doc") {
-            // Gather doc comments into about (if not explicitly set)
+            // Gather doc comments into help (if not explicitly set)
             if field_attrs.help.is_none() {
-                if let Meta::NameValue(meta_name_value) = &attr.meta {
-                    if let Expr::Lit(expr_lit) = &meta_name_value.value {
-                        if let Lit::Str(lit_str) = &expr_lit.lit {
-                            // Accumulate multiple lines into one string
-                            let line = lit_str.value();
-                            let trimmed = line.trim();
-                            if !trimmed.is_empty() {
-                                match &mut field_attrs.help {
-                                    Some(existing) => {
-                                        existing.push(' ');
-                                        existing.push_str(trimmed);
-                                    }
-                                    None => {
-                                        field_attrs.help = Some(trimmed.to_string());
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+                append_doc_comment(attr, &mut field_attrs.help);
             }
         }
     }
@@ -141,6 +239,25 @@ pub fn parse_field_attributes(field: &Field) -> Result<FieldAttributes, Error> {
     Ok(field_attrs)
 }
 
+// Explicitly required, or a plain `T` field without default that cannot be built when missing,
+// conditional requirements and defaults are left to the command
+pub fn is_required(field: &Field, field_attrs: &FieldAttributes) -> bool {
+    let is_conditional = !field_attrs.required_if_eq.is_empty()
+        || !field_attrs.required_unless_present.is_empty()
+        || !field_attrs.default_value_if.is_empty();
+
+    field_attrs.required || !(
+        is_option_type(&field.ty)
+            || is_conditional
+            || is_vec_type(&field.ty)
+            || is_bool_type(&field.ty)
+            || field_attrs.count
+            || field_attrs.last
+            || field_attrs.action.as_ref().is_some_and(|action| FLAG_ACTIONS.iter().any(|flag| action == flag))
+            || field_attrs.default_value.is_some()
+    )
+}
+
 pub fn generate_field_parsers(fields: &FieldsNamed) -> Result<Vec<proc_macro2::TokenStream>, Error> {
     let mut parsers = Vec::new();
 
@@ -173,9 +290,22 @@ pub fn generate_field_parsers(fields: &FieldsNamed) -> Result<Vec<proc_macro2::T
                 }
             }
             else if is_bool {
-                // Boolean flags don't take values
+                // Boolean flags don't take values, a SetFalse flag defaults to true
+                let default_flag = field_attrs.action.as_ref().is_some_and(|action| action == "SetFalse");
                 quote! {
-                    let #var_name = parsed.contains_key(#field_name_str);
+                    let #var_name = parsed.get_flag(#field_name_str).unwrap_or(#default_flag);
+                }
+            }
+            else if is_option_bool_type(&field.ty) {
+                // Tri-state flag: None when not provided
+                quote! {
+                    let #var_name = parsed.get_flag(#field_name_str);
+                }
+            }
+            else if is_vec_vec_type(&field.ty) {
+                // Values grouped by occurrence
+                quote! {
+                    let #var_name = parsed.get_occurrences(#field_name_str);
                 }
             }
             else if is_vec {
@@ -184,10 +314,16 @@ pub fn generate_field_parsers(fields: &FieldsNamed) -> Result<Vec<proc_macro2::T
                     let #var_name = parsed.get_all(#field_name_str);
                 }
             }
+            else if get_array_type(&field.ty).is_some() {
+                // Values of the first occurrence
+                quote! {
+                    let #var_name = parsed.get_occurrences(#field_name_str).into_iter().next();
+                }
+            }
             else if field_attrs.required && !is_optional {
+                // Checked by the conversion, so that the other fields are still converted
                 quote! {
-                    let #var_name = parsed.get(#field_name_str)
-                        .ok_or_else(|| ::no_std_clap_core::error::ParseError::MissingArgument(#field_name_str.to_string()))?;
+                    let #var_name = parsed.get(#field_name_str).map(|s| s.as_str());
                 }
             }
             else if let Some(default) = &field_attrs.default_value {
@@ -219,69 +355,119 @@ pub fn generate_field_parsers(fields: &FieldsNamed) -> Result<Vec<proc_macro2::T
     Ok(parsers)
 }
 
-pub fn generate_field_assignments(fields: &FieldsNamed) -> Result<Vec<proc_macro2::TokenStream>, Error> {
-    let mut assignments = Vec::new();
+// Convert every field into a `field_<name>` option. Conversion errors go to `parse_errors`,
+// the next fields are still converted when the command collects errors
+pub fn generate_field_conversions(fields: &FieldsNamed) -> Result<Vec<proc_macro2::TokenStream>, Error> {
+    let mut conversions = Vec::new();
 
     for field in &fields.named {
         let field_name = field.ident.as_ref().unwrap();
         let field_attrs = parse_field_attributes(field)?;
 
         if field_attrs.skip {
-            // Use Default::default() for skipped fields
-            assignments.push(quote! {
-                #field_name: ::core::default::Default::default()
-            });
             continue;
         }
 
         let var_name = format_ident!("parsed_{}", field_name);
+        let converted_name = format_ident!("field_{}", field_name);
         let field_type = &field.ty;
+        let arg_display_name = display_name(field_name, &field_attrs);
 
         let is_optional = is_option_type(field_type);
 
         if field_attrs.subcommand {
             let assignment = if is_optional {
                 quote! {
-                    #field_name: match #var_name {
+                    match #var_name {
                         Some((name, args)) => {
-                            <#field_type as Subcommand>::from_subcommand(name, None, args)?
+                            <#field_type as Subcommand>::from_subcommand(name, None, args, &context.subcommand(name))?
                         },
                         None => None,
-                    },
+                    }
                 }
             }
             else {
                 quote! {
-                    #field_name: {
-                        if let Some((name, args)) = #var_name {
-                            <#field_type as Subcommand>::from_subcommand(name, None, args)?
-                        }
-                        else {
-                            return Err(::no_std_clap_core::error::ParseError::UnknownSubcommand);
-                        }
-                    },
+                    if let Some((name, args)) = #var_name {
+                        <#field_type as Subcommand>::from_subcommand(name, None, args, &context.subcommand(name))?
+                    }
+                    else {
+                        let names = <#field_type as Subcommand>::subcommand_info().into_iter().map(|info| info.name).collect();
+                        return Err(::no_std_clap_core::error::ParseError::new(::no_std_clap_core::error::ErrorKind::MissingSubcommand).expected(names));
+                    }
                 }
             };
 
-            assignments.push(assignment);
+            // Subcommands are resolved first so a help request there wins over missing arguments here
+            conversions.insert(0, wrap_conversion(&converted_name, field_type, assignment));
         }
         else {
             let is_vec = is_vec_type(field_type);
             let is_bool = is_bool_type(field_type);
 
-            let mut assignment = if is_bool || field_attrs.count {
+            let is_flag = is_bool || is_option_bool_type(field_type) || field_attrs.count;
+
+            let conversion = if field_attrs.count {
+                // Counters can be any integer type, saturating like clap's `Count`
+                quote! {
+                    <#field_type as ::core::convert::TryFrom<usize>>::try_from(#var_name).unwrap_or(<#field_type>::MAX)
+                }
+            }
+            else if is_flag {
+                quote! {
+                    #var_name
+                }
+            }
+            else if is_vec_vec_type(field_type) {
+                // For Vec<Vec<T>>, parse each occurrence into its own vector
+                let inner_type = get_inner_type(field_type).and_then(get_inner_type).unwrap_or(field_type);
+                let convert = convert_value(quote! { value }, inner_type, &field_attrs, &arg_display_name);
                 quote! {
-                    #field_name: #var_name
+                    {
+                        let mut vec = ::alloc::vec::Vec::new();
+                        for occurrence in #var_name {
+                            let mut values = ::alloc::vec::Vec::new();
+                            for value in occurrence {
+                                values.push(#convert);
+                            }
+                            vec.push(values);
+                        }
+                        vec
+                    }
+                }
+            }
+            else if let Some((elem_type, len)) = get_array_type(field_type) {
+                // For [T; N], the command already checked the number of values
+                let convert = convert_value(quote! { value }, elem_type, &field_attrs, &arg_display_name);
+                quote! {
+                    {
+                        let occurrence = #var_name.ok_or_else(||
+                            ::no_std_clap_core::error::ParseError::new(::no_std_clap_core::error::ErrorKind::MissingArgument)
+                                .arg(stringify!(#field_name))
+                        )?;
+                        let mut values = ::alloc::vec::Vec::new();
+                        for value in occurrence {
+                            values.push(#convert);
+                        }
+                        let count = values.len();
+                        <[#elem_type; #len]>::try_from(values).map_err(|_|
+                            ::no_std_clap_core::error::ParseError::new(::no_std_clap_core::error::ErrorKind::TooFewValues)
+                                .arg(stringify!(#field_name))
+                                .min_values(#len)
+                                .num_values(count)
+                        )?
+                    }
                 }
             }
             else if is_vec {
                 // For Vec<T>, parse each value and collect into a vector
                 let inner_type = get_inner_type(field_type).unwrap_or(field_type);
+                let convert = convert_value(quote! { value }, inner_type, &field_attrs, &arg_display_name);
                 quote! {
-                    #field_name: {
+                    {
                         let mut vec = ::alloc::vec::Vec::new();
                         for value in #var_name {
-                            vec.push(<#inner_type as FromArg>::from_arg(value)?);
+                            vec.push(#convert);
                         }
                         vec
                     }
@@ -290,46 +476,131 @@ pub fn generate_field_assignments(fields: &FieldsNamed) -> Result<Vec<proc_macro
             else if is_optional {
                 // For Option<T>, we need to get the inner type T
                 let inner_type = get_inner_type(field_type).unwrap_or(field_type);
+                let convert = convert_value(quote! { s }, inner_type, &field_attrs, &arg_display_name);
                 quote! {
-                    #field_name: match #var_name {
-                        Some(s) => Some(<#inner_type as FromArg>::from_arg(s)?),
+                    match #var_name {
+                        Some(s) => Some(#convert),
                         None => None,
                     }
                 }
             }
-            else if field_attrs.required || field_attrs.default_value.is_some() {
+            else if field_attrs.required {
+                let convert = convert_value(quote! { s }, field_type, &field_attrs, &arg_display_name);
                 quote! {
-                    #field_name: <#field_type as FromArg>::from_arg(#var_name)?
+                    {
+                        let s = #var_name.ok_or_else(||
+                            ::no_std_clap_core::error::ParseError::new(::no_std_clap_core::error::ErrorKind::MissingArgument)
+                                .arg(stringify!(#field_name))
+                        )?;
+                        #convert
+                    }
                 }
             }
-            else if is_optional {
-                let inner_type = get_inner_type(field_type).unwrap_or(field_type);
+            else if field_attrs.default_value.is_some() {
+                let convert = convert_value(quote! { #var_name }, field_type, &field_attrs, &arg_display_name);
                 quote! {
-                    #field_name: match #var_name {
-                        Some(s) => Some(<#inner_type as FromArg>::from_arg(s)?),
-                        None => None,
-                    }
+                    #convert
                 }
             }
             else {
                 // Non-optional, non-required, no default -> error at runtime
+                let convert = convert_value(quote! { s }, field_type, &field_attrs, &arg_display_name);
                 quote! {
-                    #field_name: {
+                    {
                         let s = #var_name.ok_or_else(||
-                            ::no_std_clap_core::error::ParseError::MissingArgument(
-                                stringify!(#field_name).to_string()
-                            )
+                            ::no_std_clap_core::error::ParseError::new(::no_std_clap_core::error::ErrorKind::MissingArgument)
+                                .arg(stringify!(#field_name))
                         )?;
-                        <#field_type as FromArg>::from_arg(s)?
+                        #convert
                     }
                 }
             };
 
-            assignment.extend(quote! { , });
+            // `FromArg::from_arg_with` is given the argument of the field, the one the command owns
+            let conversion = match is_flag || field_attrs.value_parser.is_some() {
+                true => conversion,
+                false => {
+                    let field_name_str = field_name.to_string();
+                    quote! {
+                        {
+                            let arg_info = context.arg(#field_name_str);
+                            #conversion
+                        }
+                    }
+                }
+            };
 
-            assignments.push(assignment);
+            conversions.push(wrap_conversion(&converted_name, field_type, conversion));
+        }
+    }
+
+    Ok(conversions)
+}
+
+// `let field_<name> = ...;` holding `Some` value unless the conversion failed
+fn wrap_conversion(converted_name: &Ident, field_type: &Type, conversion: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    quote! {
+        #[allow(clippy::redundant_closure_call, clippy::needless_question_mark)]
+        let #converted_name = match (|| -> ::core::result::Result<#field_type, ::no_std_clap_core::error::ParseError> { Ok(#conversion) })() {
+            Ok(value) => Some(value),
+            Err(error) => {
+                parse_errors.recover(error, context.collect_errors)?;
+                None
+            }
+        };
+    }
+}
+
+// Fields of the struct built from the conversions, only used once `parse_errors` is empty
+pub fn generate_field_assignments(fields: &FieldsNamed) -> Result<Vec<proc_macro2::TokenStream>, Error> {
+    let mut assignments = Vec::new();
+
+    for field in &fields.named {
+        let field_name = field.ident.as_ref().unwrap();
+        let field_attrs = parse_field_attributes(field)?;
+
+        if field_attrs.skip {
+            // Use Default::default() for skipped fields
+            assignments.push(quote! {
+                #field_name: ::core::default::Default::default(),
+            });
+        }
+        else {
+            let converted_name = format_ident!("field_{}", field_name);
+            assignments.push(quote! {
+                #field_name: #converted_name.unwrap(),
+            });
         }
     }
 
     Ok(assignments)
 }
+
+// Name of the argument as typed by the user, the same as `ArgInfo::display_name`
+fn display_name(field_name: &Ident, field_attrs: &FieldAttributes) -> String {
+    match (&field_attrs.long, field_attrs.short) {
+        (Some(long), _) => format!("--{}", long),
+        (None, Some(short)) => format!("-{}", short),
+        (None, None) => format!("<{}>", field_name.to_string().to_uppercase()),
+    }
+}
+
+// Conversion of one value, through the field's value parser when there is one
+fn convert_value(value: proc_macro2::TokenStream, ty: &Type, field_attrs: &FieldAttributes, arg_display_name: &str) -> proc_macro2::TokenStream {
+    match &field_attrs.value_parser {
+        Some(value_parser) => quote! {
+            #value_parser(#value).map_err(|error| ::no_std_clap_core::error::ParseError::new(::no_std_clap_core::error::ErrorKind::InvalidArgumentValue)
+                .arg(#arg_display_name)
+                .value(#value)
+                .message(::alloc::string::ToString::to_string(&error))
+                .with_source(error)
+            )?
+        },
+        None => quote! {
+            match arg_info {
+                Some(arg_info) => <#ty as FromArg>::from_arg_with(#value, arg_info),
+                None => <#ty as FromArg>::from_arg(#value),
+            }.map_err(|error| error.arg(#arg_display_name))?
+        },
+    }
+}
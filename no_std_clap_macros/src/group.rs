@@ -0,0 +1,107 @@
+use crate::field::parse_field_attributes;
+use quote::quote;
+use syn::{Attribute, Error, FieldsNamed, LitBool, LitStr, Meta, Token};
+
+struct GroupAttributes {
+    name: String,
+    required: bool,
+    multiple: bool,
+}
+
+// Parse `#[group(name = "...", required, multiple)]`, the name defaults to the struct name
+fn parse_group_attributes(attrs: &[Attribute], default_name: &str) -> Result<Vec<GroupAttributes>, Error> {
+    let mut groups = Vec::new();
+
+    for attr in attrs {
+        if attr.path().is_ident("group") {
+            let mut group_attrs = GroupAttributes {
+                name: default_name.to_string(),
+                required: false,
+                multiple: false,
+            };
+
+            if let Meta::List(_) = &attr.meta {
+                attr.parse_nested_meta(|meta| {
+                    // Either `required` or `required = true`
+                    let flag = |meta: &syn::meta::ParseNestedMeta| -> Result<bool, Error> {
+                        match meta.input.peek(Token![=]) {
+                            true => Ok(meta.value()?.parse::<LitBool>()?.value),
+                            false => Ok(true),
+                        }
+                    };
+
+                    if meta.path.is_ident("name") || meta.path.is_ident("id") {
+                        let value: LitStr = meta.value()?.parse()?;
+                        group_attrs.name = value.value();
+                    }
+                    else if meta.path.is_ident("required") {
+                        group_attrs.required = flag(&meta)?;
+                    }
+                    else if meta.path.is_ident("multiple") {
+                        group_attrs.multiple = flag(&meta)?;
+                    }
+                    Ok(())
+                })?;
+            }
+
+            groups.push(group_attrs);
+        }
+    }
+
+    Ok(groups)
+}
+
+// Generate the ArgGroup of every declared group and of every group named by a field
+pub fn generate_group_definitions(attrs: &[Attribute], fields: &FieldsNamed, default_name: &str) -> Result<Vec<proc_macro2::TokenStream>, Error> {
+    let mut groups = parse_group_attributes(attrs, default_name)?;
+    let mut members: Vec<Vec<String>> = groups.iter().map(|_| Vec::new()).collect();
+
+    for field in &fields.named {
+        let field_attrs = parse_field_attributes(field)?;
+
+        let Some(group_name) = field_attrs.group
+        else {
+            continue;
+        };
+
+        let position = match groups.iter().position(|group| group.name == group_name) {
+            Some(position) => position,
+            None => {
+                // Group only named by its fields
+                groups.push(GroupAttributes {
+                    name: group_name,
+                    required: false,
+                    multiple: false,
+                });
+                members.push(Vec::new());
+                groups.len() - 1
+            }
+        };
+
+        members[position].push(field.ident.as_ref().unwrap().to_string());
+    }
+
+    let definitions = groups.iter().zip(members).map(|(group, members)| {
+        let group_name = &group.name;
+        let mut definition = quote! {
+            ::no_std_clap_core::arg::arg_group::ArgGroup::new(#group_name)
+                #(.arg(#members))*
+        };
+
+        if group.required {
+            definition.extend(quote! {
+                .required()
+            });
+        }
+
+        if group.multiple {
+            definition.extend(quote! {
+                .multiple()
+            });
+        }
+
+        definition
+    });
+
+    Ok(definitions.collect())
+}
@@ -5,6 +5,8 @@ mod subcommand;
 mod args;
 mod enum_values;
 mod field;
+mod group;
+mod collision;
 
 use crate::args::derive_args_impl;
 use crate::enum_values::derive_enum_values_arg_impl;
@@ -13,21 +15,21 @@ use crate::subcommand::derive_subcommand_impl;
 use proc_macro::TokenStream;
 use syn::{parse_macro_input, DeriveInput};
 
-#[proc_macro_derive(Parser, attributes(arg, clap, command))]
+#[proc_macro_derive(Parser, attributes(arg, clap, command, group))]
 pub fn derive_parser(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
     derive_parser_impl(input).unwrap_or_else(|err| err.to_compile_error().into())
 }
 
-#[proc_macro_derive(Subcommand, attributes(command))]
+#[proc_macro_derive(Subcommand, attributes(arg, command, group))]
 pub fn derive_subcommand(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
     derive_subcommand_impl(input).unwrap_or_else(|err| err.to_compile_error().into())
 }
 
-#[proc_macro_derive(Args, attributes(arg, clap))]
+#[proc_macro_derive(Args, attributes(arg, clap, group))]
 pub fn derive_args(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
@@ -1,16 +1,24 @@
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{Attribute, Data, DeriveInput, Error, Expr, Fields, Lit, LitStr, Meta};
+use syn::{Attribute, Data, DeriveInput, Error, Expr, Fields, Ident, LitStr, Meta, Path};
 use crate::args::{generate_arg_definitions, generate_global_arg_definitions};
-use crate::field::{generate_field_assignments, generate_field_parsers};
+use crate::collision::{check_flag_collisions, generate_global_collision_assertions, HELP_FLAG, VERSION_FLAG};
+use crate::field::{generate_field_assignments, generate_field_conversions, generate_field_parsers};
+use crate::group::generate_group_definitions;
 use crate::subcommand::generate_subcommand_definitions;
-use crate::utils::to_kebab_case_case;
+use crate::utils::{append_doc_comment, to_kebab_case_case};
 
 struct StructAttributes {
     name: Option<String>,
     author: Option<String>,
     version: Option<String>,
     about: Option<String>,
+    infer_long_args: bool,
+    infer_subcommands: bool,
+    override_policy: Option<Ident>,
+    collect_errors: bool,
+    // `&'static dyn Messages` expression, e.g. `&French`
+    messages: Option<Expr>,
 }
 
 pub fn derive_parser_impl(input: DeriveInput) -> Result<TokenStream, Error> {
@@ -18,9 +26,10 @@ pub fn derive_parser_impl(input: DeriveInput) -> Result<TokenStream, Error> {
 
     // Parse struct attributes
     let struct_attrs = parse_struct_attributes(&input.attrs)?;
+    let default_group_name = to_kebab_case_case(name.to_string());
     let (app_name, app_name_kebab_case) = match struct_attrs.name {
         Some(name) => {
-            let name_kebab_case = to_kebab_case_case(name.to_string());
+            let name_kebab_case = to_kebab_case_case(&name);
 
             (
                 quote! { Some(#name) },
@@ -35,25 +44,80 @@ pub fn derive_parser_impl(input: DeriveInput) -> Result<TokenStream, Error> {
         None => quote! { None }
     };
 
-    let version = match struct_attrs.version {
+    let version = match &struct_attrs.version {
         Some(version) => quote! { Some(#version) },
         None => quote! { None }
     };
 
+    // `-V/--version` is only added when there is a version to print
+    let version_arg_definition = match &struct_attrs.version {
+        Some(_) => quote! {
+            cmd = cmd.arg(
+                ArgInfo::new("version")
+                    .short('V')
+                    .long("version")
+                    .action(::no_std_clap_core::arg::arg_action::ArgAction::Version)
+            );
+        },
+        None => quote! {}
+    };
+
+    let reserved_flags = match struct_attrs.version {
+        Some(_) => vec![HELP_FLAG, VERSION_FLAG],
+        None => vec![HELP_FLAG],
+    };
+
     let about = match struct_attrs.about {
         Some(about) => quote! { Some(#about) },
         None => quote! { None }
     };
 
+    // Used by the help as well as by the parse
+    let messages_setting = match &struct_attrs.messages {
+        Some(messages) => quote! { cmd = cmd.messages(#messages); },
+        None => quote! {}
+    };
+
+    let mut settings = quote! { #messages_setting };
+
+    if struct_attrs.infer_long_args {
+        settings.extend(quote! {
+            cmd = cmd.infer_long_args();
+        });
+    }
+
+    if struct_attrs.infer_subcommands {
+        settings.extend(quote! {
+            cmd = cmd.infer_subcommands();
+        });
+    }
+
+    if struct_attrs.collect_errors {
+        settings.extend(quote! {
+            cmd = cmd.collect_errors();
+        });
+    }
+
+    if let Some(policy) = &struct_attrs.override_policy {
+        settings.extend(quote! {
+            cmd = cmd.override_policy(::no_std_clap_core::command::OverridePolicy::#policy);
+        });
+    }
+
     match input.data {
         Data::Struct(data_struct) => {
             match data_struct.fields {
                 Fields::Named(fields) => {
+                    check_flag_collisions(&fields, &reserved_flags)?;
+
+                    let global_collision_assertions = generate_global_collision_assertions(&fields)?;
                     let field_parsers = generate_field_parsers(&fields)?;
+                    let field_conversions = generate_field_conversions(&fields)?;
                     let field_assignments = generate_field_assignments(&fields)?;
                     let arg_definitions = generate_arg_definitions(&fields)?;
                     let global_arg_definitions = generate_global_arg_definitions(&fields)?;
                     let subcommand_definitions = generate_subcommand_definitions(&fields, &global_arg_definitions)?;
+                    let group_definitions = generate_group_definitions(&input.attrs, &fields, &default_group_name)?;
 
                     let expanded = quote! {
                         impl ::no_std_clap_core::parser::Parser for #name {
@@ -65,25 +129,40 @@ pub fn derive_parser_impl(input: DeriveInput) -> Result<TokenStream, Error> {
                                 use ::no_std_clap_core::parser::{Subcommand, Args};
 
                                 let mut cmd = Command::new(#app_name_kebab_case, #author, #version, #about);
+                                #settings
 
                                 #(cmd = cmd.arg(#arg_definitions);)*
                                 cmd = cmd.arg(
                                     ArgInfo::new("help")
                                         .short('h')
                                         .long("help")
-                                        .help("Prints help information")
+                                        .action(::no_std_clap_core::arg::arg_action::ArgAction::Help)
                                         .global()
                                 );
+                                #version_arg_definition
+                                #(cmd = cmd.group(#group_definitions);)*
 
                                 #(#subcommand_definitions)*
 
-                                let parsed = cmd.parse(args)?;
+                                // Every error leaving the parse gets the usage line of its level
+                                #[allow(clippy::redundant_closure_call)]
+                                (|| -> ::core::result::Result<Self, ::no_std_clap_core::error::ParseError> {
+                                    let mut parse_errors = ::no_std_clap_core::error::ParseErrors::new();
+                                    let parsed = cmd.parse_collecting(args, &mut parse_errors)?;
+                                    let context = &cmd.parse_context();
+
+                                    if parsed.contains_key("help") {
+                                        return Err(::no_std_clap_core::error::ParseError::new(::no_std_clap_core::error::ErrorKind::Help).message(<Self as ::no_std_clap_core::parser::Parser>::get_help()));
+                                    }
 
-                                #(#field_parsers)*
+                                    #(#field_parsers)*
+                                    #(#field_conversions)*
+                                    parse_errors.into_result(())?;
 
-                                Ok(Self {
-                                    #(#field_assignments)*
-                                })
+                                    Ok(Self {
+                                        #(#field_assignments)*
+                                    })
+                                })().map_err(|error| cmd.complete_error(error))
                             }
 
                             fn get_help() -> ::alloc::string::String {
@@ -94,21 +173,26 @@ pub fn derive_parser_impl(input: DeriveInput) -> Result<TokenStream, Error> {
                                 use ::no_std_clap_core::parser::{Subcommand, Args};
 
                                 let mut cmd = Command::new(#app_name, #author, #version, #about);
+                                #messages_setting
 
                                 #(cmd = cmd.arg(#arg_definitions);)*
                                 cmd = cmd.arg(
                                     ArgInfo::new("help")
                                         .short('h')
                                         .long("help")
-                                        .help("Prints help information")
+                                        .action(::no_std_clap_core::arg::arg_action::ArgAction::Help)
                                         .global()
                                 );
+                                #version_arg_definition
+                                #(cmd = cmd.group(#group_definitions);)*
 
                                 #(#subcommand_definitions)*
 
                                 cmd.get_help()
                             }
                         }
+
+                        #(#global_collision_assertions)*
                     };
 
                     Ok(TokenStream::from(expanded))
@@ -129,58 +213,64 @@ fn parse_struct_attributes(attrs: &[Attribute]) -> Result<StructAttributes, Erro
         version: None,
         author: None,
         about: None,
+        infer_long_args: false,
+        infer_subcommands: false,
+        override_policy: None,
+        collect_errors: false,
+        messages: None,
     };
 
     for attr in attrs {
         if attr.path().is_ident("clap") {
-            match &attr.meta {
-                Meta::List(_meta_list) => {
-                    attr.parse_nested_meta(|meta| {
-                        if meta.path.is_ident("name") {
-                            let value: LitStr = meta.value()?.parse()?;
-                            struct_attrs.name = Some(value.value());
-                        }
-                        else if meta.path.is_ident("version") {
-                            let value: LitStr = meta.value()?.parse()?;
-                            struct_attrs.version = Some(value.value());
-                        }
-                        else if meta.path.is_ident("author") {
-                            let value: LitStr = meta.value()?.parse()?;
-                            struct_attrs.author = Some(value.value());
-                        }
-                        else if meta.path.is_ident("about") {
-                            let value: LitStr = meta.value()?.parse()?;
-                            struct_attrs.about = Some(value.value());
+            if let Meta::List(_) = &attr.meta {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("name") {
+                        let value: LitStr = meta.value()?.parse()?;
+                        struct_attrs.name = Some(value.value());
+                    }
+                    else if meta.path.is_ident("version") {
+                        let value: LitStr = meta.value()?.parse()?;
+                        struct_attrs.version = Some(value.value());
+                    }
+                    else if meta.path.is_ident("author") {
+                        let value: LitStr = meta.value()?.parse()?;
+                        struct_attrs.author = Some(value.value());
+                    }
+                    else if meta.path.is_ident("about") {
+                        let value: LitStr = meta.value()?.parse()?;
+                        struct_attrs.about = Some(value.value());
+                    }
+                    else if meta.path.is_ident("infer_long_args") {
+                        struct_attrs.infer_long_args = true;
+                    }
+                    else if meta.path.is_ident("infer_subcommands") {
+                        struct_attrs.infer_subcommands = true;
+                    }
+                    else if meta.path.is_ident("collect_errors") {
+                        struct_attrs.collect_errors = true;
+                    }
+                    else if meta.path.is_ident("messages") {
+                        struct_attrs.messages = Some(meta.value()?.parse()?);
+                    }
+                    else if meta.path.is_ident("override_policy") {
+                        // Either `override_policy = FirstWins` or `override_policy = OverridePolicy::FirstWins`
+                        let value: Path = meta.value()?.parse()?;
+                        let policy = value.segments.last().unwrap().ident.clone();
+
+                        if policy != "FirstWins" && policy != "LastWins" {
+                            return Err(Error::new_spanned(value, "unknown override policy, expected one of: FirstWins, LastWins"));
                         }
-                        Ok(())
-                    })?;
-                }
-                _ => {}
+
+                        struct_attrs.override_policy = Some(policy);
+                    }
+                    Ok(())
+                })?;
             }
         }
         else if attr.path().is_ident("doc") {
             // Gather doc comments into about (if not explicitly set)
             if struct_attrs.about.is_none() {
-                if let Meta::NameValue(meta_name_value) = &attr.meta {
-                    if let Expr::Lit(expr_lit) = &meta_name_value.value {
-                        if let Lit::Str(lit_str) = &expr_lit.lit {
-                            // Accumulate multiple lines into one string
-                            let line = lit_str.value();
-                            let trimmed = line.trim();
-                            if !trimmed.is_empty() {
-                                match &mut struct_attrs.about {
-                                    Some(existing) => {
-                                        existing.push(' ');
-                                        existing.push_str(trimmed);
-                                    }
-                                    None => {
-                                        struct_attrs.about = Some(trimmed.to_string());
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+                append_doc_comment(attr, &mut struct_attrs.about);
             }
         }
     }
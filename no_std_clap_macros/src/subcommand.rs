@@ -1,9 +1,11 @@
-use crate::args::{generate_arg_info_for_args, generate_args_field_assignments, generate_args_field_parsers};
-use crate::field::parse_field_attributes;
-use crate::utils::{get_inner_type, to_kebab_case_case};
+use crate::args::generate_arg_info_for_args;
+use crate::collision::{check_flag_collisions, check_subcommand_collisions, generate_flag_set, HELP_FLAG};
+use crate::field::{generate_field_assignments, generate_field_conversions, generate_field_parsers, parse_field_attributes};
+use crate::group::generate_group_definitions;
+use crate::utils::{append_doc_comment, get_inner_type, to_kebab_case_case};
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{Data, DataEnum, DeriveInput, Error, Expr, Fields, FieldsNamed, Lit, LitStr, Meta, Variant};
+use syn::{Data, DataEnum, DeriveInput, Error, Fields, FieldsNamed, LitStr, Meta, Variant};
 
 #[derive(Default)]
 struct SubcommandVariantAttributes {
@@ -16,22 +18,35 @@ pub fn derive_subcommand_impl(input: DeriveInput) -> Result<TokenStream, Error>
 
     match input.data {
         Data::Enum(data_enum) => {
+            let flag_set = generate_subcommand_flag_set(&data_enum)?;
             let match_arms = generate_subcommand_match_arms(&data_enum)?;
             let subcommand_info_arms = generate_subcommand_info_arms(&data_enum)?;
 
             let expanded = quote! {
                 impl ::no_std_clap_core::parser::Subcommand for #name {
-                    fn from_subcommand(name: &str, parents_name: Option<::alloc::string::String>, args: &::no_std_clap_core::arg::parsed_arg::ParsedArgs) -> ::core::result::Result<Self, ::no_std_clap_core::error::ParseError> {
+                    const FLAGS: ::no_std_clap_core::parser::FlagSet = #flag_set;
+
+                    fn from_subcommand(name: &str, parents_name: Option<::alloc::string::String>, args: &::no_std_clap_core::arg::parsed_arg::ParsedArgs, context: &::no_std_clap_core::parser::ParseContext) -> ::core::result::Result<Self, ::no_std_clap_core::error::ParseError> {
                         use ::no_std_clap_core::parser::Args;
 
-                        match name {
-                            #(#match_arms)*
-                            _ => Err(::no_std_clap_core::error::ParseError::UnknownArgument(::alloc::string::String::from(name))),
-                        }
+                        // Errors of the fields are tagged with the subcommand they come from, deeper ones keep their own path
+                        let path = match &parents_name {
+                            Some(parents_name) => ::alloc::format!("{} {}", parents_name, name),
+                            None => ::alloc::string::String::from(name),
+                        };
+
+                        #[allow(clippy::redundant_closure_call)]
+                        (|| -> ::core::result::Result<Self, ::no_std_clap_core::error::ParseError> {
+                            match name {
+                                #(#match_arms)*
+                                _ => Err(::no_std_clap_core::error::ParseError::new(::no_std_clap_core::error::ErrorKind::UnknownSubcommand).value(name)),
+                            }
+                        })().map_err(|error| error.subcommand(&path))
                     }
 
                     fn subcommand_info() -> ::alloc::vec::Vec<::no_std_clap_core::subcommand::SubcommandInfo> {
                         use ::no_std_clap_core::subcommand::SubcommandInfo;
+                        use ::no_std_clap_core::arg::arg_info::ArgInfo;
                         use ::no_std_clap_core::parser::Args;
 
                         ::alloc::vec![
@@ -61,7 +76,7 @@ pub fn generate_subcommand_definitions(fields: &FieldsNamed, global_arg_defs: &[
             let field_type = &field.ty;
 
             // Extract the inner type from Option<T> if it's optional
-            let subcommand_type = get_inner_type(field_type).unwrap_or_else(|| field_type);
+            let subcommand_type = get_inner_type(field_type).unwrap_or(field_type);
 
             let definition = quote! {
                 {
@@ -75,7 +90,7 @@ pub fn generate_subcommand_definitions(fields: &FieldsNamed, global_arg_defs: &[
                             ArgInfo::new("help")
                                 .short('h')
                                 .long("help")
-                                .help("Prints help information")
+                                .action(::no_std_clap_core::arg::arg_action::ArgAction::Help)
                                 .global()
                         );
 
@@ -90,6 +105,49 @@ pub fn generate_subcommand_definitions(fields: &FieldsNamed, global_arg_defs: &[
     Ok(definitions)
 }
 
+// Check the variants for collisions and gather their flags
+fn generate_subcommand_flag_set(data_enum: &DataEnum) -> Result<proc_macro2::TokenStream, Error> {
+    let mut names = Vec::new();
+    let mut children = Vec::new();
+
+    for variant in &data_enum.variants {
+        let variant_attrs = parse_subcommand_variant_attributes(variant)?;
+        names.push(variant_attrs.name.unwrap_or_else(|| to_kebab_case_case(variant.ident.to_string())));
+
+        match &variant.fields {
+            Fields::Unit => {}
+            Fields::Unnamed(fields) => {
+                let field = &fields.unnamed.first().unwrap();
+                let field_type = &field.ty;
+                let field_attrs = parse_field_attributes(field)?;
+
+                if fields.unnamed.len() == 1 && (field_attrs.subcommand || enum_variant_is_subcommand(variant)) {
+                    children.push(quote! { &<#field_type as ::no_std_clap_core::parser::Subcommand>::FLAGS });
+                }
+                else {
+                    children.push(quote! { &<#field_type as ::no_std_clap_core::parser::Args>::FLAGS });
+                }
+            }
+            Fields::Named(fields) => {
+                check_flag_collisions(fields, &[HELP_FLAG])?;
+
+                let flag_set = generate_flag_set(fields)?;
+                children.push(quote! { &#flag_set });
+            }
+        }
+    }
+
+    check_subcommand_collisions(data_enum, &names)?;
+
+    Ok(quote! {
+        ::no_std_clap_core::parser::FlagSet {
+            shorts: &[],
+            longs: &[],
+            children: &[#(#children),*],
+        }
+    })
+}
+
 // Generate match arms for subcommand enum variants
 fn generate_subcommand_match_arms(data_enum: &DataEnum) -> Result<Vec<proc_macro2::TokenStream>, Error> {
     let mut arms = Vec::new();
@@ -123,7 +181,7 @@ fn generate_subcommand_match_arms(data_enum: &DataEnum) -> Result<Vec<proc_macro
                                 };
 
                                 Ok(Self::#variant_name(
-                                    <#field_type as ::no_std_clap_core::parser::Subcommand>::from_subcommand(sub_name, Some(parents_name), sub_args)?
+                                    <#field_type as ::no_std_clap_core::parser::Subcommand>::from_subcommand(sub_name, Some(parents_name), sub_args, &context.subcommand(sub_name))?
                                 ))
                             }
                             else {
@@ -131,9 +189,9 @@ fn generate_subcommand_match_arms(data_enum: &DataEnum) -> Result<Vec<proc_macro
                                     .into_iter()
                                     .find(|info| info.name == name)
                                     .unwrap()
-                                    .get_help(parents_name);
+                                    .get_help(parents_name, context.messages);
 
-                                Err(::no_std_clap_core::error::ParseError::Help(help))
+                                Err(::no_std_clap_core::error::ParseError::new(::no_std_clap_core::error::ErrorKind::Help).message(help))
                             }
                         },
                     });
@@ -147,11 +205,11 @@ fn generate_subcommand_match_arms(data_enum: &DataEnum) -> Result<Vec<proc_macro
                     // Plain Args struct
                     arms.push(quote! {
                         #command_name => if args.args.is_empty() || args.args.contains_key("help") {
-                            let help = <#field_type as ::no_std_clap_core::parser::Args>::get_help(::alloc::string::String::from(name), parents_name, #about);
-                            Err(::no_std_clap_core::error::ParseError::Help(help))
+                            let help = <#field_type as ::no_std_clap_core::parser::Args>::get_help(::alloc::string::String::from(name), parents_name, #about, context.messages);
+                            Err(::no_std_clap_core::error::ParseError::new(::no_std_clap_core::error::ErrorKind::Help).message(help))
                         }
                         else {
-                            Ok(Self::#variant_name(<#field_type as ::no_std_clap_core::parser::Args>::from_args(args)?))
+                            Ok(Self::#variant_name(<#field_type as ::no_std_clap_core::parser::Args>::from_args(args, context)?))
                         },
                     });
                 }
@@ -168,21 +226,41 @@ fn generate_subcommand_match_arms(data_enum: &DataEnum) -> Result<Vec<proc_macro
                 // Plain Args struct
                 arms.push(quote! {
                     #command_name => if args.args.is_empty() || args.args.contains_key("help") {
-                        let help = <#field_type as ::no_std_clap_core::parser::Args>::get_help(::alloc::string::String::from(name), parents_name, #about);
-                        Err(::no_std_clap_core::error::ParseError::Help(help))
+                        let help = <#field_type as ::no_std_clap_core::parser::Args>::get_help(::alloc::string::String::from(name), parents_name, #about, context.messages);
+                        Err(::no_std_clap_core::error::ParseError::new(::no_std_clap_core::error::ErrorKind::Help).message(help))
                     }
                     else {
-                        Ok(Self::#variant_name(<#field_type as ::no_std_clap_core::parser::Args>::from_args(args)?))
+                        Ok(Self::#variant_name(<#field_type as ::no_std_clap_core::parser::Args>::from_args(args, context)?))
                     },
                 });
             },
             Fields::Named(fields) => {
-                let field_parsers = generate_args_field_parsers(fields)?;
-                let field_assignments = generate_args_field_assignments(fields)?;
+                let field_parsers = generate_field_parsers(fields)?;
+                let field_conversions = generate_field_conversions(fields)?;
+                let field_assignments = generate_field_assignments(fields)?;
 
                 arms.push(quote! {
                     #command_name => {
+                        use ::no_std_clap_core::arg::from_arg::FromArg;
+                        use ::no_std_clap_core::parser::Subcommand;
+
+                        if args.args.contains_key("help") {
+                            let help = <Self as ::no_std_clap_core::parser::Subcommand>::subcommand_info()
+                                .into_iter()
+                                .find(|info| info.name == name)
+                                .unwrap()
+                                .get_help(parents_name, context.messages);
+
+                            return Err(::no_std_clap_core::error::ParseError::new(::no_std_clap_core::error::ErrorKind::Help).message(help));
+                        }
+
+                        let parsed = args;
+                        let mut parse_errors = ::no_std_clap_core::error::ParseErrors::new();
+
                         #(#field_parsers)*
+                        #(#field_conversions)*
+                        parse_errors.into_result(())?;
+
                         Ok(Self::#variant_name {
                             #(#field_assignments)*
                         })
@@ -238,6 +316,9 @@ fn generate_subcommand_info_arms(data_enum: &DataEnum) -> Result<Vec<proc_macro2
                             for arg_info in arg_infos {
                                 info = info.arg(arg_info);
                             }
+                            for group in <#field_type as Args>::group_info() {
+                                info = info.group(group);
+                            }
                             info
                         },
                     });
@@ -245,14 +326,16 @@ fn generate_subcommand_info_arms(data_enum: &DataEnum) -> Result<Vec<proc_macro2
             }
             Fields::Named(fields) => {
                 let arg_info_generation = generate_arg_info_for_args(fields)?;
+                let group_definitions = generate_group_definitions(&variant.attrs, fields, &command_name)?;
 
                 arms.push(quote! {
                     {
                         let mut info = SubcommandInfo::new(#command_name).about(#about);
-                        let arg_infos = ::alloc::vec![#(#arg_info_generation),*];
+                        let arg_infos = ::alloc::vec![#(#arg_info_generation)*];
                         for arg_info in arg_infos {
                             info = info.arg(arg_info);
                         }
+                        #(info = info.group(#group_definitions);)*
                         info
                     },
                 });
@@ -275,46 +358,24 @@ fn parse_subcommand_variant_attributes(variant: &Variant) -> Result<SubcommandVa
 
     for attr in &variant.attrs {
         if attr.path().is_ident("command") {
-            match &attr.meta {
-                Meta::List(_) => {
-                    attr.parse_nested_meta(|meta| {
-                        if meta.path.is_ident("name") {
-                            let value: LitStr = meta.value()?.parse()?;
-                            variant_attrs.name = Some(value.value());
-                        }
-                        else if meta.path.is_ident("about") {
-                            let value: LitStr = meta.value()?.parse()?;
-                            variant_attrs.about = Some(value.value());
-                        }
-                        Ok(())
-                    })?;
-                }
-                _ => {}
+            if let Meta::List(_) = &attr.meta {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("name") {
+                        let value: LitStr = meta.value()?.parse()?;
+                        variant_attrs.name = Some(value.value());
+                    }
+                    else if meta.path.is_ident("about") {
+                        let value: LitStr = meta.value()?.parse()?;
+                        variant_attrs.about = Some(value.value());
+                    }
+                    Ok(())
+                })?;
             }
         }
         else if attr.path().is_ident("doc") {
             // Gather doc comments into about (if not explicitly set)
             if variant_attrs.about.is_none() {
-                if let Meta::NameValue(meta_name_value) = &attr.meta {
-                    if let Expr::Lit(expr_lit) = &meta_name_value.value {
-                        if let Lit::Str(lit_str) = &expr_lit.lit {
-                            // Accumulate multiple lines into one string
-                            let line = lit_str.value();
-                            let trimmed = line.trim();
-                            if !trimmed.is_empty() {
-                                match &mut variant_attrs.about {
-                                    Some(existing) => {
-                                        existing.push(' ');
-                                        existing.push_str(trimmed);
-                                    }
-                                    None => {
-                                        variant_attrs.about = Some(trimmed.to_string());
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+                append_doc_comment(attr, &mut variant_attrs.about);
             }
         }
     }
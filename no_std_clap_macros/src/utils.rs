@@ -1,4 +1,4 @@
-use syn::{GenericArgument, PathArguments, Type};
+use syn::{Attribute, Expr, GenericArgument, Lit, Meta, PathArguments, Type};
 
 pub fn to_kebab_case_case<T: AsRef<str>>(input: T) -> String {
     let text = input.as_ref();
@@ -12,7 +12,7 @@ pub fn to_kebab_case_case<T: AsRef<str>>(input: T) -> String {
         let mut n1: (bool, char) = (first.is_lowercase(), first);
 
         for c in text {
-            let prev_n1 = n1.clone();
+            let prev_n1 = n1;
 
             let n3 = n2;
             n2 = Some(n1);
@@ -44,43 +44,96 @@ pub fn to_kebab_case_case<T: AsRef<str>>(input: T) -> String {
 }
 
 pub fn get_inner_type(ty: &Type) -> Option<&Type> {
-    if let Type::Path(type_path) = ty {
-        if let Some(segment) = type_path.path.segments.last() {
-            if segment.ident == "Option" || segment.ident == "Vec" {
-                if let PathArguments::AngleBracketed(args) = &segment.arguments {
-                    if let Some(GenericArgument::Type(inner_ty)) = args.args.first() {
-                        return Some(inner_ty);
-                    }
-                }
-            }
-        }
+    if let Type::Path(type_path) = ty
+        && let Some(segment) = type_path.path.segments.last()
+        && (segment.ident == "Option" || segment.ident == "Vec")
+        && let PathArguments::AngleBracketed(args) = &segment.arguments
+        && let Some(GenericArgument::Type(inner_ty)) = args.args.first() {
+        return Some(inner_ty);
     }
     None
 }
 
 pub fn is_option_type(ty: &Type) -> bool {
-    if let Type::Path(type_path) = ty {
-        if let Some(segment) = type_path.path.segments.last() {
-            return segment.ident == "Option";
-        }
+    if let Type::Path(type_path) = ty
+        && let Some(segment) = type_path.path.segments.last() {
+        return segment.ident == "Option";
     }
     false
 }
 
 pub fn is_vec_type(ty: &Type) -> bool {
-    if let Type::Path(type_path) = ty {
-        if let Some(segment) = type_path.path.segments.last() {
-            return segment.ident == "Vec";
-        }
+    if let Type::Path(type_path) = ty
+        && let Some(segment) = type_path.path.segments.last() {
+        return segment.ident == "Vec";
     }
     false
 }
 
 pub fn is_bool_type(ty: &Type) -> bool {
-    if let Type::Path(type_path) = ty {
-        if let Some(segment) = type_path.path.segments.last() {
-            return segment.ident == "bool";
-        }
+    if let Type::Path(type_path) = ty
+        && let Some(segment) = type_path.path.segments.last() {
+        return segment.ident == "bool";
+    }
+    false
+}
+
+pub fn is_option_bool_type(ty: &Type) -> bool {
+    is_option_type(ty) && get_inner_type(ty).is_some_and(is_bool_type)
+}
+
+// `Vec<Vec<T>>`, values grouped by occurrence
+pub fn is_vec_vec_type(ty: &Type) -> bool {
+    is_vec_type(ty) && get_inner_type(ty).is_some_and(is_vec_type)
+}
+
+// `[T; N]`, returns the element type and the length
+pub fn get_array_type(ty: &Type) -> Option<(&Type, &Expr)> {
+    if let Type::Array(type_array) = ty {
+        return Some((&type_array.elem, &type_array.len));
+    }
+    None
+}
+
+// Type of each value of a field, e.g. `u8` for `Option<u8>`, `Vec<Vec<u8>>` or `[u8; 3]`
+pub fn get_value_type(ty: &Type) -> &Type {
+    if let Some((elem, _)) = get_array_type(ty) {
+        return get_value_type(elem);
+    }
+
+    match get_inner_type(ty) {
+        Some(inner_ty) => get_value_type(inner_ty),
+        None => ty,
+    }
+}
+
+pub fn is_number_type(ty: &Type) -> bool {
+    if let Type::Path(type_path) = ty
+        && let Some(segment) = type_path.path.segments.last() {
+        return ["i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize", "f32", "f64"]
+            .iter()
+            .any(|number| segment.ident == number);
     }
     false
 }
+
+// Accumulate a `///` doc comment line into `target`, multiple lines are joined with a space
+pub fn append_doc_comment(attr: &Attribute, target: &mut Option<String>) {
+    if let Meta::NameValue(meta_name_value) = &attr.meta
+        && let Expr::Lit(expr_lit) = &meta_name_value.value
+        && let Lit::Str(lit_str) = &expr_lit.lit {
+        let line = lit_str.value();
+        let trimmed = line.trim();
+        if !trimmed.is_empty() {
+            match target {
+                Some(existing) => {
+                    existing.push(' ');
+                    existing.push_str(trimmed);
+                }
+                None => {
+                    *target = Some(trimmed.to_string());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use alloc::string::{String, ToString};
+use no_std_clap_core::arg::arg_action::ArgAction;
+use no_std_clap_core::arg::arg_info::ArgInfo;
+use no_std_clap_core::command::Command;
+use no_std_clap_core::error::{ErrorKind, ParseError};
+use no_std_clap_core::parser::{parse_command_line, Parser};
+use no_std_clap_macros::{Parser, Subcommand};
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "flasher", version = "2.1.0")]
+struct Flasher {
+    #[arg(short, long)]
+    name: Option<String>,
+
+    #[arg(short, long)]
+    verbose: bool,
+
+    #[arg(long)]
+    color: Option<bool>,
+
+    #[arg(long, action = SetFalse)]
+    verify: bool,
+
+    #[arg(short, long, count)]
+    quiet: usize,
+
+    #[arg(short, long, action = Count)]
+    debug: u8,
+
+    #[command(subcommand)]
+    command: Option<FlasherCommand>,
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+enum FlasherCommand {
+    Write {
+        #[arg(short, long)]
+        address: u32,
+
+        #[arg(short, long)]
+        erase: bool,
+    },
+}
+
+#[test]
+fn test_flag_values() {
+    let flasher = Flasher::parse_str("--name yes").unwrap();
+    assert_eq!(flasher.name, Some("yes".to_string()));
+    assert!(!flasher.verbose);
+    assert_eq!(flasher.color, None);
+    assert!(flasher.verify);
+
+    let flasher = Flasher::parse_str("--verbose=false --color=false --verify").unwrap();
+    assert!(!flasher.verbose);
+    assert_eq!(flasher.color, Some(false));
+    assert!(!flasher.verify);
+
+    let flasher = Flasher::parse_str("-vqq --color").unwrap();
+    assert!(flasher.verbose);
+    assert_eq!(flasher.quiet, 2);
+    assert_eq!(flasher.color, Some(true));
+
+    // Flags never take the next token as value
+    assert!(matches!(Flasher::parse_str("--verbose true"), Err(ParseError { kind: ErrorKind::UnknownArgument, .. })));
+    assert!(matches!(Flasher::parse_str("--verbose=maybe"), Err(ParseError { kind: ErrorKind::InvalidValue, .. })));
+    assert!(matches!(Flasher::parse_str("--quiet=2"), Err(ParseError { kind: ErrorKind::UnexpectedValue, .. })));
+    assert!(matches!(Flasher::parse_str("--name"), Err(ParseError { kind: ErrorKind::MissingValue, .. })));
+}
+
+#[test]
+fn test_count_action() {
+    // `action = Count` is the same as `count`
+    let flasher = Flasher::parse_str("-dd --debug").unwrap();
+    assert_eq!(flasher.debug, 3);
+
+    let flasher = Flasher::parse_str("-v").unwrap();
+    assert_eq!(flasher.debug, 0);
+}
+
+#[test]
+fn test_help_and_version() {
+    assert!(matches!(Flasher::parse_str("--help"), Err(ParseError { kind: ErrorKind::Help, .. })));
+
+    match Flasher::parse_str("-V") {
+        Err(ParseError { kind: ErrorKind::Version, context }) => assert_eq!(context.message.as_deref(), Some("flasher 2.1.0")),
+        _ => panic!("Expected version"),
+    }
+}
+
+#[test]
+fn test_named_variant() {
+    let flasher = Flasher::parse_str("write --address 4096 -e").unwrap();
+    assert_eq!(flasher.command, Some(FlasherCommand::Write { address: 4096, erase: true }));
+}
+
+#[test]
+fn test_builder_actions() {
+    let cmd = Command::new(Some("builder"), None, None, None)
+        .arg(ArgInfo::new("all").short('a').action(ArgAction::SetTrue))
+        .arg(ArgInfo::new("level").short('l').action(ArgAction::Set))
+        .arg(ArgInfo::new("tag").short('t').action(ArgAction::Append));
+
+    let args = parse_command_line("-al3 -t one -t=two").unwrap();
+    let parsed = cmd.parse(&args).unwrap();
+    assert_eq!(parsed.get_flag("all"), Some(true));
+    assert_eq!(parsed.get("level"), Some(&"3".to_string()));
+    assert_eq!(parsed.get_all("tag"), ["one", "two"]);
+}
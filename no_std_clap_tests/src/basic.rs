@@ -1,10 +1,11 @@
 use alloc::string::{String, ToString};
 use alloc::vec;
 use alloc::vec::Vec;
+use no_std_clap_core::arg::arg_action::ArgAction;
 use no_std_clap_core::arg::arg_info::ArgInfo;
 use no_std_clap_core::arg::from_arg::FromArg;
 use no_std_clap_core::command::Command;
-use no_std_clap_core::error::ParseError;
+use no_std_clap_core::error::{ErrorKind, ParseError};
 use no_std_clap_core::parser::Parser;
 
 #[derive(Debug, PartialEq)]
@@ -19,10 +20,10 @@ struct TestArgs {
 // Manual implementation of what the derive macro would generate
 impl Parser for TestArgs {
     fn parse_args(args: &[String]) -> Result<Self, ParseError> {
-        let mut cmd = Command::new(Some("test"), Some("Julien-cpsn"), Some("0.1.0"), None)
+        let cmd = Command::new(Some("test"), Some("Julien-cpsn"), Some("0.1.0"), None)
             .arg(ArgInfo::new("name").long("name").short('n').required())
             .arg(ArgInfo::new("count").long("count").short('c').required())
-            .arg(ArgInfo::new("verbose").long("verbose").short('v').global())
+            .arg(ArgInfo::new("verbose").long("verbose").short('v').action(ArgAction::SetTrue).global())
             .arg(ArgInfo::new("list").long("list").short('l').multiple())
             .arg(ArgInfo::new("optional").long("optional").short('o'));
 
@@ -30,13 +31,13 @@ impl Parser for TestArgs {
 
         let name = parsed
             .get("name")
-            .ok_or_else(|| ParseError::MissingArgument("name".to_string()))?;
+            .ok_or_else(|| ParseError::new(ErrorKind::MissingArgument).arg("name"))?;
 
         let count_str = parsed
             .get("count")
-            .ok_or_else(|| ParseError::MissingArgument("count".to_string()))?;
+            .ok_or_else(|| ParseError::new(ErrorKind::MissingArgument).arg("count"))?;
 
-        let verbose = parsed.contains_key("verbose");
+        let verbose = parsed.get_flag("verbose").unwrap_or(false);
 
         let list = parsed
             .get_all("list")
@@ -61,7 +62,7 @@ impl Parser for TestArgs {
         let cmd = Command::new(Some("test"), Some("Julien-cpsn"), Some("0.1.0"), None)
             .arg(ArgInfo::new("name").long("name").short('n').required())
             .arg(ArgInfo::new("count").long("count").short('c').required())
-            .arg(ArgInfo::new("verbose").long("verbose").short('v').global())
+            .arg(ArgInfo::new("verbose").long("verbose").short('v').action(ArgAction::SetTrue).global())
             .arg(ArgInfo::new("list").long("list").short('l').multiple())
             .arg(ArgInfo::new("optional").long("optional").short('o'));
 
@@ -90,4 +91,52 @@ fn test_basic_parsing() {
 
     let result_2 = TestArgs::parse_str("--name test --count 42 --list 5 --verbose").unwrap();
     assert_eq!(result_1, result_2);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_attached_values() {
+    let result_1 = TestArgs::parse_str("--name=test --count=-4 -l=5 -o=extra --verbose").unwrap();
+    assert_eq!(result_1.name, "test");
+    assert_eq!(result_1.count, -4);
+    assert!(result_1.verbose);
+    assert_eq!(result_1.list, vec![5]);
+    assert_eq!(result_1.optional, Some("extra".to_string()));
+
+    let result_2 = TestArgs::parse_str("--name test --count -4 -l 5 -o extra --verbose").unwrap();
+    assert_eq!(result_1, result_2);
+
+    let result_3 = TestArgs::parse_str("-n=a=b --count=0").unwrap();
+    assert_eq!(result_3.name, "a=b");
+    assert_eq!(result_3.count, 0);
+}
+
+#[test]
+fn test_command_reuse() {
+    extern crate std;
+    use no_std_clap_core::parser::parse_command_line;
+    use std::sync::LazyLock;
+
+    static CMD: LazyLock<Command> = LazyLock::new(|| {
+        Command::new(Some("shell"), None, None, None)
+            .arg(ArgInfo::new("verbose").short('v').action(ArgAction::SetTrue))
+            .arg(ArgInfo::new("source"))
+            .arg(ArgInfo::new("destination"))
+    });
+
+    for line in ["a b", "-v c d", "e f -v"] {
+        let args = parse_command_line(line).unwrap();
+        let parsed = CMD.parse(&args).unwrap();
+        assert!(parsed.get("source").is_some());
+        assert!(parsed.get("destination").is_some());
+    }
+
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                let args = parse_command_line("-v src dst").unwrap();
+                let parsed = CMD.parse(&args).unwrap();
+                assert_eq!(parsed.get("destination"), Some(&"dst".to_string()));
+            });
+        }
+    });
+}
@@ -0,0 +1,97 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use no_std_clap_core::arg::arg_action::ArgAction;
+use no_std_clap_core::arg::arg_info::ArgInfo;
+use no_std_clap_core::command::Command;
+use no_std_clap_core::error::{ErrorKind, ParseError};
+use no_std_clap_core::parser::{parse_command_line, Parser};
+use no_std_clap_macros::{Args, Parser, Subcommand};
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "flash", collect_errors)]
+struct Flash {
+    #[arg(short, long)]
+    baud: u32,
+
+    #[arg(short, long)]
+    offset: Option<u16>,
+
+    #[arg(short, long, required)]
+    port: String,
+
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+enum Commands {
+    Erase(EraseArgs),
+}
+
+#[derive(Args, Debug, PartialEq)]
+struct EraseArgs {
+    #[arg(short, long)]
+    sector: u8,
+
+    #[arg(short, long)]
+    count: u8,
+}
+
+fn command() -> Command {
+    Command::new(Some("tool"), None, None, None)
+        .collect_errors()
+        .arg(ArgInfo::new("mode").long("mode").possible_values(&["fast", "safe"]))
+        .arg(ArgInfo::new("dry_run").long("dry-run").action(ArgAction::SetTrue).conflicts_with("force"))
+        .arg(ArgInfo::new("force").long("force").action(ArgAction::SetTrue))
+        .arg(ArgInfo::new("input").long("input").required())
+        .arg(ArgInfo::new("help").long("help").action(ArgAction::Help))
+}
+
+#[test]
+fn test_collect_command_errors() {
+    let args = parse_command_line("--bogus --mode slow --dry-run --force extra").unwrap();
+
+    match command().parse(&args) {
+        Err(ParseError { kind: ErrorKind::Multiple, context }) => {
+            let errors: Vec<&ParseError> = context.errors.iter().collect();
+            assert_eq!(errors.len(), 5);
+            let kinds: Vec<ErrorKind> = errors.iter().map(|error| error.kind).collect();
+            assert_eq!(kinds, [ErrorKind::UnknownArgument, ErrorKind::UnknownArgument, ErrorKind::UnknownEnumVariant, ErrorKind::ArgumentConflict, ErrorKind::MissingArguments]);
+            assert_eq!(errors[0].context.arg.as_deref(), Some("bogus"));
+            assert_eq!(errors[1].context.arg.as_deref(), Some("extra"));
+            assert_eq!(errors[2].context.value.as_deref(), Some("slow"));
+            assert_eq!(errors[3].context.expected, ["--force"]);
+            assert_eq!(errors[4].context.expected, ["--input"]);
+        }
+        Err(other) => panic!("unexpected error: {:?}", other),
+        Ok(_) => panic!("unexpected success"),
+    }
+
+    // A single error is returned as is, an explicit help request wins over the errors
+    let args = parse_command_line("--input a --bogus").unwrap();
+    assert!(matches!(command().parse(&args), Err(ParseError { kind: ErrorKind::UnknownArgument, context }) if context.arg.as_deref() == Some("bogus")));
+
+    let args = parse_command_line("--bogus --help").unwrap();
+    assert!(command().parse(&args).unwrap().is_present("help"));
+}
+
+#[test]
+fn test_collect_derive_errors() {
+    match Flash::parse_str("--baud fast -o -1 --bogus erase -s x -c y") {
+        Err(ParseError { kind: ErrorKind::Multiple, context }) => {
+            let messages: Vec<String> = context.errors.into_iter().map(|error| error.to_string()).collect();
+            assert_eq!(messages, [
+                "Unknown argument: bogus",
+                "Missing required arguments: --port\n\nUsage: flash erase [OPTIONS]",
+                "Invalid value: Cannot parse 'x' as u8",
+                "Invalid value: Cannot parse 'y' as u8",
+                "Invalid value: Cannot parse 'fast' as u32",
+                "Invalid value: Cannot parse '-1' as u16",
+            ]);
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    let flash = Flash::parse_str("--baud 9600 -p /dev/ttyUSB0 erase -s 1 -c 2").unwrap();
+    assert_eq!(flash.command, Some(Commands::Erase(EraseArgs { sector: 1, count: 2 })));
+}
@@ -0,0 +1,60 @@
+use alloc::string::String;
+use no_std_clap_core::parser::{Args, Parser, Subcommand};
+use no_std_clap_macros::{Args, Parser, Subcommand};
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "tool")]
+struct Cli {
+    #[arg(short, long, global)]
+    verbose: bool,
+
+    #[arg(short = 'N', long)]
+    name: Option<String>,
+
+    #[arg(short = "n", long, negatable)]
+    network: bool,
+
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+enum Commands {
+    Push(PushArgs),
+    Pull {
+        #[arg(short, long)]
+        rebase: bool,
+    },
+}
+
+#[derive(Args, Debug, PartialEq)]
+struct PushArgs {
+    #[arg(short, long)]
+    force: bool,
+
+    // The same global declared again is not a collision
+    #[arg(short, long)]
+    verbose: bool,
+}
+
+#[test]
+fn test_char_short() {
+    let cli = Cli::parse_str("-N test -n -v").unwrap();
+    assert_eq!(cli.name, Some(String::from("test")));
+    assert!(cli.network);
+    assert!(cli.verbose);
+}
+
+#[test]
+fn test_flag_sets() {
+    assert!(PushArgs::FLAGS.short_collides('f', "other"));
+    assert!(!PushArgs::FLAGS.short_collides('f', "force"));
+    assert!(!PushArgs::FLAGS.short_collides('v', "verbose"));
+
+    assert!(Commands::FLAGS.long_collides("rebase", "other"));
+    assert!(Commands::FLAGS.short_collides('f', "other"));
+    assert!(!Commands::FLAGS.long_collides("help", "other"));
+
+    let cli = Cli::parse_str("push -f -v").unwrap();
+    assert_eq!(cli.command, Some(Commands::Push(PushArgs { force: true, verbose: true })));
+}
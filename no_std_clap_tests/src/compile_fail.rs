@@ -0,0 +1,6 @@
+// Each derive diagnostic, the expected compiler output is next to its case in `ui/`
+#[test]
+fn test_derive_diagnostics() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("ui/*.rs");
+}
@@ -0,0 +1,119 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use no_std_clap_core::arg::arg_action::ArgAction;
+use no_std_clap_core::arg::arg_info::ArgInfo;
+use no_std_clap_core::command::Command;
+use no_std_clap_core::error::{ErrorKind, ParseError};
+use no_std_clap_core::parser::{parse_command_line, Parser};
+use no_std_clap_core::subcommand::SubcommandInfo;
+use no_std_clap_macros::Parser;
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "flasher")]
+struct Flasher {
+    #[arg(short, long)]
+    mode: Option<String>,
+
+    #[arg(short, long, required_if_eq("mode", "raw"))]
+    address: Option<u32>,
+
+    #[arg(short, long, required_unless_present = "simulate")]
+    port: Option<String>,
+
+    #[arg(long)]
+    simulate: bool,
+
+    #[arg(long, default_value_if("mode", "fast", "8"), default_value = "1")]
+    jobs: u8,
+}
+
+#[test]
+fn test_required_if_eq() {
+    let flasher = Flasher::parse_str("--mode raw --address 4096 --port /dev/ttyACM0").unwrap();
+    assert_eq!(flasher.address, Some(4096));
+
+    let flasher = Flasher::parse_str("--mode safe --port /dev/ttyACM0").unwrap();
+    assert_eq!(flasher.address, None);
+
+    match Flasher::parse_str("--mode raw --port /dev/ttyACM0") {
+        Err(ParseError { kind: ErrorKind::MissingArguments, context }) => assert_eq!(context.expected, vec!["--address"]),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_required_unless_present() {
+    let flasher = Flasher::parse_str("--simulate").unwrap();
+    assert!(flasher.simulate);
+    assert_eq!(flasher.port, None);
+
+    match Flasher::parse_str("--mode raw") {
+        Err(ParseError { kind: ErrorKind::MissingArguments, context }) => assert_eq!(context.expected, vec!["--address", "--port"]),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_default_value_if() {
+    let flasher = Flasher::parse_str("--mode fast --simulate").unwrap();
+    assert_eq!(flasher.jobs, 8);
+
+    let flasher = Flasher::parse_str("--mode safe --simulate").unwrap();
+    assert_eq!(flasher.jobs, 1);
+
+    // An explicit value wins over the conditional default
+    let flasher = Flasher::parse_str("--mode fast --jobs 2 --simulate").unwrap();
+    assert_eq!(flasher.jobs, 2);
+}
+
+#[test]
+fn test_builder_conditions() {
+    let cmd = Command::new(Some("serial"), None, None, None)
+        .arg(ArgInfo::new("verbose").short('v').action(ArgAction::SetTrue))
+        .arg(ArgInfo::new("log").long("log").required_if_eq("verbose", "true").default_value_if("verbose", "false", "none"));
+
+    let args = parse_command_line("-v").unwrap();
+    assert!(matches!(cmd.parse(&args), Err(ParseError { kind: ErrorKind::MissingArguments, .. })));
+
+    let args = parse_command_line("-v=false").unwrap();
+    let parsed = cmd.parse(&args).unwrap();
+    assert_eq!(parsed.get("log"), Some(&"none".to_string()));
+}
+
+#[test]
+fn test_conditional_default_constraints() {
+    // A conditional default is checked like a given value
+    let cmd = Command::new(Some("link"), None, None, None)
+        .arg(ArgInfo::new("mode").long("mode"))
+        .arg(ArgInfo::new("key").long("key"))
+        .arg(ArgInfo::new("cipher").long("cipher").requires("key").default_value_if("mode", "secure", "aes"));
+
+    let args = parse_command_line("--mode secure").unwrap();
+    match cmd.parse(&args) {
+        Err(ParseError { kind: ErrorKind::MissingRequirement, context }) => {
+            assert_eq!(context.arg.as_deref(), Some("--cipher"));
+            assert_eq!(context.expected, vec!["--key"]);
+        },
+        _ => panic!("Expected a missing requirement"),
+    }
+
+    let args = parse_command_line("--mode secure --key k").unwrap();
+    let parsed = cmd.parse(&args).unwrap();
+    assert_eq!(parsed.get("cipher"), Some(&"aes".to_string()));
+}
+
+#[test]
+fn test_global_conditional_default() {
+    // Added once on the top level, whatever level has the condition
+    let cmd = Command::new(Some("flasher"), None, None, None)
+        .arg(ArgInfo::new("mode").long("mode").global())
+        .arg(ArgInfo::new("level").long("level").global().default_value_if("mode", "fast", "3"))
+        .subcommand(SubcommandInfo::new("write"));
+
+    let args = parse_command_line("write --mode fast").unwrap();
+    let parsed = cmd.parse(&args).unwrap();
+    assert_eq!(parsed.get_all("level"), ["3"]);
+
+    let (_, write) = parsed.get_subcommand().unwrap();
+    assert!(!write.is_present("level"));
+}
@@ -0,0 +1,86 @@
+use alloc::string::ToString;
+use alloc::vec;
+use no_std_clap_core::arg::arg_action::ArgAction;
+use no_std_clap_core::arg::arg_info::ArgInfo;
+use no_std_clap_core::command::Command;
+use no_std_clap_core::error::DefinitionError;
+use no_std_clap_core::parser::parse_command_line;
+use no_std_clap_core::subcommand::SubcommandInfo;
+
+#[test]
+fn test_validate_reports_every_error() {
+    let cmd = Command::new(Some("tool"), None, None, None)
+        .arg(ArgInfo::new("name").short('n'))
+        .arg(ArgInfo::new("number").short('n').long("number"))
+        .arg(ArgInfo::new("files").multiple())
+        .arg(ArgInfo::new("output"))
+        .arg(ArgInfo::new("verbose").short('v').count().multiple())
+        .arg(ArgInfo::new("mode").possible_values(&["fast", "build"]).index(1))
+        .arg(ArgInfo::new("input").index(0))
+        .subcommand(SubcommandInfo::new("build")
+            .arg(ArgInfo::new("target").long("number"))
+            .arg(ArgInfo::new("release").long("target"))
+            .arg(ArgInfo::new("jobs").long("target"))
+        );
+
+    let errors = cmd.validate().unwrap_err();
+    assert_eq!(errors, vec![
+        DefinitionError::DuplicateShort("tool".to_string(), 'n', "name".to_string(), "number".to_string()),
+        DefinitionError::CountAndMultiple("tool".to_string(), "verbose".to_string()),
+        DefinitionError::ZeroIndex("tool".to_string(), "input".to_string()),
+        DefinitionError::PositionalAfterVariadic("tool".to_string(), "files".to_string(), "output".to_string()),
+        DefinitionError::SubcommandShadowsPositional("tool".to_string(), "build".to_string(), "mode".to_string()),
+        DefinitionError::DuplicateLong("tool build".to_string(), "target".to_string(), "release".to_string(), "jobs".to_string()),
+    ]);
+    assert_eq!(errors[0].to_string(), "tool: -n is used by both name and number");
+}
+
+#[test]
+#[allow(clippy::reversed_empty_ranges)]
+fn test_validate_value_range() {
+    let cmd = Command::new(Some("tool"), None, None, None)
+        .arg(ArgInfo::new("point").long("point").num_args(3..1))
+        .arg(ArgInfo::new("nothing").long("nothing").num_args(0..0))
+        .arg(ArgInfo::new("color").long("color").num_args(0..=1));
+
+    let errors = cmd.validate().unwrap_err();
+    assert_eq!(errors, vec![
+        DefinitionError::EmptyValueRange("tool".to_string(), "point".to_string(), 3, 0),
+        DefinitionError::EmptyValueRange("tool".to_string(), "nothing".to_string(), 0, 0),
+    ]);
+    assert_eq!(errors[0].to_string(), "tool: point takes an empty range of 3..=0 values");
+}
+
+#[test]
+fn test_validate_propagated_globals() {
+    let verbose = ArgInfo::new("verbose").short('v').action(ArgAction::SetTrue).global();
+
+    // The same global copied into a subcommand is not a collision, another argument using its short is
+    let cmd = Command::new(Some("tool"), None, None, None)
+        .arg(verbose.clone())
+        .subcommand(SubcommandInfo::new("add").arg(verbose.clone()))
+        .subcommand(SubcommandInfo::new("remove").arg(ArgInfo::new("value").short('v')));
+
+    let errors = cmd.validate().unwrap_err();
+    assert_eq!(errors, vec![
+        DefinitionError::DuplicateShort("tool remove".to_string(), 'v', "value".to_string(), "verbose".to_string()),
+    ]);
+
+    let cmd = Command::new(Some("tool"), None, None, None)
+        .arg(verbose.clone())
+        .subcommand(SubcommandInfo::new("add").arg(verbose));
+
+    assert!(cmd.validate().is_ok());
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "Invalid command definition:\ntool: --all is used by both all and any")]
+fn test_parse_asserts_definition() {
+    let cmd = Command::new(Some("tool"), None, None, None)
+        .arg(ArgInfo::new("all").long("all").action(ArgAction::SetTrue))
+        .arg(ArgInfo::new("any").long("all").action(ArgAction::SetTrue));
+
+    let args = parse_command_line("--all").unwrap();
+    let _ = cmd.parse(&args);
+}
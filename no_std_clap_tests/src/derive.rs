@@ -1,7 +1,7 @@
 use alloc::string::{String, ToString};
 use alloc::vec;
 use alloc::vec::Vec;
-use no_std_clap_core::error::ParseError;
+use no_std_clap_core::error::{ErrorKind, ParseError};
 use no_std_clap_core::parser::Parser;
 use no_std_clap_macros::Parser;
 
@@ -11,7 +11,7 @@ struct Args {
     #[arg(short, long, help = "Name to use")]
     name: String,
 
-    #[arg(short, long, required)]
+    #[arg(short = 'N', long, required)]
     number: i32,
 
     #[arg(short, long, count, global)]
@@ -70,7 +70,16 @@ fn test_help_parsing() {
         "--help".to_string(),
     ];
 
-    if let Err(ParseError::Help(help)) = Args::parse_args(&args) {
-        assert!(!help.is_empty());
+    if let Err(ParseError { kind: ErrorKind::Help, context }) = Args::parse_args(&args) {
+        assert!(!context.message.unwrap_or_default().is_empty());
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_derive_attached_values() {
+    let result = Args::parse_str("--name=test --number=-42 --list=5 -l=6 --optional=7").unwrap();
+    assert_eq!(result.name, "test");
+    assert_eq!(result.number, -42);
+    assert_eq!(result.list, vec![5, 6]);
+    assert_eq!(result.optional, Some(7));
+}
@@ -0,0 +1,91 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use no_std_clap_core::error::{ErrorContext, ErrorKind, ParseError};
+use no_std_clap_core::parser::Parser;
+use no_std_clap_macros::{Args, Parser, Subcommand};
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "modem")]
+struct Modem {
+    #[arg(short, long)]
+    baud: Option<u32>,
+
+    #[command(subcommand)]
+    command: Option<ModemCommand>,
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+enum ModemCommand {
+    Dial(DialArgs),
+}
+
+#[derive(Args, Debug, PartialEq)]
+struct DialArgs {
+    #[arg(short, long)]
+    number: String,
+
+    #[arg(short, long)]
+    retries: Option<u8>,
+}
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "bridge")]
+struct Bridge {
+    #[arg(short, long)]
+    verbose: bool,
+
+    #[command(subcommand)]
+    command: BridgeCommand,
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+enum BridgeCommand {
+    Link {
+        #[arg(short, long)]
+        force: bool,
+    },
+    Status,
+}
+
+#[test]
+fn test_error_context() {
+    let error = Modem::parse_str("--baud fast").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::InvalidValue);
+    assert_eq!(error.context.arg.as_deref(), Some("--baud"));
+    assert_eq!(error.context.value.as_deref(), Some("fast"));
+    assert_eq!(error.to_string(), "Invalid value: Cannot parse 'fast' as u32");
+
+    let error = Modem::parse_str("dial -n 555 --redial").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::UnknownArgument);
+    assert_eq!(error.context.arg.as_deref(), Some("redial"));
+    assert_eq!(error.context.subcommand.as_deref(), Some("dial"));
+    assert_eq!(error.to_string(), "Unknown argument: redial");
+}
+
+#[test]
+fn test_missing_subcommand() {
+    let error = Bridge::parse_str("-v").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::MissingSubcommand);
+    assert_eq!(error.context.expected, ["link", "status"]);
+    assert_eq!(error.context.subcommand, None);
+    assert_eq!(error.to_string(), "Missing subcommand, expected one of: link, status");
+    assert!(error.context.usage.as_deref().unwrap().starts_with("Usage: bridge"));
+
+    let bridge = Bridge::parse_str("-v link -f").unwrap();
+    assert_eq!(bridge.command, BridgeCommand::Link { force: true });
+}
+
+#[test]
+fn test_error_rendering() {
+    // The context is enough to word the error differently
+    let render = |error: ParseError| -> String {
+        match (error.kind, &*error.context) {
+            (ErrorKind::InvalidValue, ErrorContext { arg: Some(arg), value: Some(value), .. }) => format!("{} can't be '{}'", arg, value),
+            (ErrorKind::UnknownArgument, ErrorContext { arg: Some(arg), subcommand: Some(subcommand), .. }) => format!("'{}' has no option {}", subcommand, arg),
+            _ => error.to_string(),
+        }
+    };
+
+    assert_eq!(render(Modem::parse_str("dial -n 555 -r many").unwrap_err()), "--retries can't be 'many'");
+    assert_eq!(render(Modem::parse_str("dial -x").unwrap_err()), "'dial' has no option x");
+}
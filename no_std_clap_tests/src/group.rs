@@ -0,0 +1,120 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use no_std_clap_core::arg::arg_action::ArgAction;
+use no_std_clap_core::arg::arg_group::ArgGroup;
+use no_std_clap_core::arg::arg_info::ArgInfo;
+use no_std_clap_core::command::Command;
+use no_std_clap_core::error::{ErrorKind, ParseError};
+use no_std_clap_core::parser::{parse_command_line, Args, Parser};
+use no_std_clap_core::subcommand::SubcommandInfo;
+use no_std_clap_macros::{Args, Parser, Subcommand};
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "fetch")]
+#[group(name = "source", required)]
+struct Fetch {
+    #[arg(short, long, group = "source")]
+    file: Option<String>,
+
+    #[arg(short, long, group = "source")]
+    url: Option<String>,
+
+    #[arg(long, group = "source")]
+    stdin: bool,
+
+    #[arg(long, group = "format")]
+    json: bool,
+
+    #[arg(long, group = "format")]
+    csv: bool,
+}
+
+#[test]
+fn test_required_group() {
+    let fetch = Fetch::parse_str("--url http://a --json").unwrap();
+    assert_eq!(fetch.url, Some("http://a".to_string()));
+    assert!(fetch.json);
+
+    let fetch = Fetch::parse_str("--stdin").unwrap();
+    assert!(fetch.stdin);
+
+    match Fetch::parse_str("--csv") {
+        Err(ParseError { kind: ErrorKind::MissingArguments, context }) => {
+            assert_eq!(context.expected, vec!["<--file|--url|--stdin>"]);
+            assert_eq!(context.usage.as_deref(), Some("Usage: fetch <--file <FILE>|--url <URL>|--stdin> [OPTIONS]"));
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_group_conflict() {
+    match Fetch::parse_str("--file a.txt --url http://a") {
+        Err(ParseError { kind: ErrorKind::GroupConflict, context }) => {
+            assert_eq!(context.group.as_deref(), Some("source"));
+            assert_eq!(context.expected, vec!["--file", "--url"]);
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    // Groups named only by their fields allow at most one argument
+    assert!(matches!(Fetch::parse_str("--stdin --json --csv"), Err(ParseError { kind: ErrorKind::GroupConflict, .. })));
+}
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "export")]
+struct Export {
+    #[command(subcommand)]
+    command: ExportCommand,
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+enum ExportCommand {
+    Save(SaveArgs),
+}
+
+#[derive(Args, Debug, PartialEq)]
+#[group(required, multiple)]
+struct SaveArgs {
+    #[arg(long, group = "save-args")]
+    disk: bool,
+
+    #[arg(long, group = "save-args")]
+    cloud: bool,
+}
+
+#[test]
+fn test_args_group() {
+    let export = Export::parse_str("save --disk --cloud").unwrap();
+    assert_eq!(export.command, ExportCommand::Save(SaveArgs { disk: true, cloud: true }));
+
+    let export = Export::parse_str("save --cloud").unwrap();
+    assert_eq!(export.command, ExportCommand::Save(SaveArgs { disk: false, cloud: true }));
+
+    // The struct level group is named after the struct
+    let groups = SaveArgs::group_info();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].name, "save-args");
+    assert_eq!(groups[0].args, vec!["disk", "cloud"]);
+    assert!(groups[0].required && groups[0].multiple);
+}
+
+#[test]
+fn test_builder_group() {
+    let cmd = Command::new(Some("out"), None, None, None)
+        .subcommand(
+            SubcommandInfo::new("print")
+                .arg(ArgInfo::new("json").long("json").action(ArgAction::SetTrue))
+                .arg(ArgInfo::new("csv").long("csv").action(ArgAction::SetTrue))
+                .group(ArgGroup::new("format").args(&["json", "csv"]).required())
+        );
+
+    let args = parse_command_line("print --csv").unwrap();
+    assert!(cmd.parse(&args).is_ok());
+
+    let args = parse_command_line("print --csv --json").unwrap();
+    assert!(matches!(cmd.parse(&args), Err(ParseError { kind: ErrorKind::GroupConflict, .. })));
+
+    let args = parse_command_line("print").unwrap();
+    assert!(matches!(cmd.parse(&args), Err(ParseError { kind: ErrorKind::MissingArguments, .. })));
+}
@@ -0,0 +1,87 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use no_std_clap_core::arg::arg_action::ArgAction;
+use no_std_clap_core::arg::arg_info::ArgInfo;
+use no_std_clap_core::command::Command;
+use no_std_clap_core::error::{ErrorKind, ParseError};
+use no_std_clap_core::parser::{parse_command_line, Parser};
+use no_std_clap_core::subcommand::SubcommandInfo;
+use no_std_clap_macros::{Parser, Subcommand};
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "console", infer_long_args, infer_subcommands)]
+struct Console {
+    #[arg(long)]
+    verbose: bool,
+
+    #[arg(long)]
+    version_check: bool,
+
+    #[arg(long)]
+    baud: Option<u32>,
+
+    #[command(subcommand)]
+    command: Option<ConsoleCommand>,
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+enum ConsoleCommand {
+    Remove,
+    Rename,
+    Reset,
+}
+
+#[test]
+fn test_infer_long_args() {
+    let console = Console::parse_str("--verb --ba=9600").unwrap();
+    assert!(console.verbose);
+    assert_eq!(console.baud, Some(9600));
+
+    // Exact names are never ambiguous
+    let console = Console::parse_str("--verbose").unwrap();
+    assert!(console.verbose);
+
+    match Console::parse_str("--ver") {
+        Err(ParseError { kind: ErrorKind::Ambiguous, context }) => {
+            assert_eq!(context.arg.as_deref(), Some("--ver"));
+            assert_eq!(context.expected, vec!["--verbose", "--version-check"]);
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_infer_subcommands() {
+    let console = Console::parse_str("rem").unwrap();
+    assert_eq!(console.command, Some(ConsoleCommand::Remove));
+
+    let console = Console::parse_str("res").unwrap();
+    assert_eq!(console.command, Some(ConsoleCommand::Reset));
+
+    match Console::parse_str("re") {
+        Err(ParseError { kind: ErrorKind::Ambiguous, context }) => {
+            assert_eq!(context.arg.as_deref(), Some("re"));
+            assert_eq!(context.expected, vec!["remove", "rename", "reset"]);
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_inference_is_opt_in() {
+    let cmd = Command::new(Some("console"), None, None, None)
+        .arg(ArgInfo::new("verbose").long("verbose").action(ArgAction::SetTrue))
+        .subcommand(SubcommandInfo::new("remove"));
+
+    let args = parse_command_line("--verb").unwrap();
+    assert!(matches!(cmd.parse(&args), Err(ParseError { kind: ErrorKind::UnknownArgument, .. })));
+
+    let args = parse_command_line("rem").unwrap();
+    assert!(matches!(cmd.parse(&args), Err(ParseError { kind: ErrorKind::UnknownArgument, .. })));
+
+    let cmd = cmd.infer_long_args().infer_subcommands();
+    let args = parse_command_line("--verb rem").unwrap();
+    let parsed = cmd.parse(&args).unwrap();
+    assert_eq!(parsed.get_flag("verbose"), Some(true));
+    assert_eq!(parsed.get_subcommand().map(|(name, _)| name.to_string()), Some(String::from("remove")));
+}
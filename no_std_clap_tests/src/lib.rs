@@ -1,6 +1,7 @@
 #![no_std]
 
 #[cfg(test)]
+#[allow(clippy::map_clone)]
 mod basic;
 #[cfg(test)]
 mod derive;
@@ -8,5 +9,51 @@ mod derive;
 mod subcommand;
 #[cfg(test)]
 mod enum_derive;
+#[cfg(test)]
+mod positional;
+#[cfg(test)]
+mod values;
+#[cfg(test)]
+mod action;
+#[cfg(test)]
+mod validation;
+#[cfg(test)]
+mod num_args;
+#[cfg(test)]
+mod infer;
+#[cfg(test)]
+mod group;
+#[cfg(test)]
+mod relation;
+#[cfg(test)]
+mod conditional;
+#[cfg(test)]
+mod overrides;
+#[cfg(test)]
+mod range;
+#[cfg(test)]
+mod value_parser;
+#[cfg(test)]
+mod possible_values;
+#[cfg(test)]
+mod collision;
+#[cfg(test)]
+mod compile_fail;
+#[cfg(test)]
+mod definition;
+#[cfg(test)]
+mod collect;
+#[cfg(test)]
+mod error;
+#[cfg(test)]
+mod suggestion;
+#[cfg(test)]
+mod span;
+#[cfg(test)]
+mod render;
+#[cfg(test)]
+mod messages;
+#[cfg(test)]
+mod try_from_arg;
 
 extern crate alloc;
@@ -0,0 +1,137 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use core::fmt;
+use no_std_clap_core::arg::arg_info::ArgInfo;
+use no_std_clap_core::command::Command;
+use no_std_clap_core::error::ErrorKind;
+use no_std_clap_core::messages::Messages;
+use no_std_clap_core::parser::{Parser, Subcommand};
+use no_std_clap_macros::{Args, Parser, Subcommand};
+
+#[derive(Debug)]
+struct German;
+
+impl Messages for German {
+    fn usage(&self) -> &str {
+        "Aufruf"
+    }
+
+    fn options(&self) -> &str {
+        "Optionen"
+    }
+
+    fn commands(&self) -> &str {
+        "Befehle"
+    }
+
+    fn help_flag(&self) -> &str {
+        "Zeigt die Hilfe an"
+    }
+
+    fn error_prefix(&self) -> &str {
+        "Fehler"
+    }
+
+    fn help_hint(&self) -> &str {
+        "Weitere Informationen mit '--help'"
+    }
+
+    fn unknown_argument(&self, arg: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unbekanntes Argument: {}", arg)
+    }
+
+    fn missing_arguments(&self, args: &[String], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Fehlende Argumente: {}", args.join(", "))
+    }
+
+    fn invalid_value(&self, value: &str, value_type: Option<&str>, message: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match value_type {
+            Some(value_type) => write!(f, "'{}' ist kein {}", value, value_type),
+            None => write!(f, "Ungültiger Wert: {}", message),
+        }
+    }
+
+    fn unexpected_value(&self, arg: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} erwartet keinen Wert", arg)
+    }
+
+    fn suggestion(&self, suggestion: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, ", meinten Sie '{}'?", suggestion)
+    }
+}
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "sensor", messages = &German)]
+struct Sensor {
+    #[arg(short, long)]
+    verbose: bool,
+
+    #[command(subcommand)]
+    command: Option<SensorCommand>,
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+enum SensorCommand {
+    Read(ReadArgs),
+}
+
+#[derive(Args, Debug, PartialEq)]
+struct ReadArgs {
+    #[arg(short, long)]
+    channel: u8,
+}
+
+#[test]
+fn test_help_messages() {
+    let help = Sensor::get_help();
+    assert!(help.contains("Optionen:"));
+    assert!(help.contains("Befehle:"));
+    assert!(help.contains("Zeigt die Hilfe an"));
+    assert!(!help.contains("Usage"));
+
+    // The help of a subcommand is written with the catalog of the command
+    let error = Sensor::parse_str("read --help").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Help);
+    assert!(error.to_string().starts_with("Aufruf: read [OPTIONS]\n\nOptionen:"));
+
+    // As well as the help of the subcommands alone
+    let help = <SensorCommand as Subcommand>::get_help(&German);
+    assert!(help.contains("Befehle:"));
+}
+
+#[test]
+fn test_error_messages() {
+    let error = Sensor::parse_str("--verbos").unwrap_err();
+    assert_eq!(error.to_string(), "Unbekanntes Argument: verbos, meinten Sie '--verbose'?");
+    assert_eq!(error.render(), "Fehler: Unbekanntes Argument: verbos, meinten Sie '--verbose'?\n\nAufruf: sensor [OPTIONS] [SUBCOMMAND]\n\nWeitere Informationen mit '--help'");
+
+    let error = Sensor::parse_str("read").unwrap_err();
+    assert_eq!(error.to_string(), "Fehlende Argumente: --channel\n\nAufruf: sensor read [OPTIONS]");
+
+    // The built-in conversions and flags give the catalog the parts of their message
+    let error = Sensor::parse_str("read -c x").unwrap_err();
+    assert_eq!(error.to_string(), "'x' ist kein u8");
+
+    let error = Sensor::parse_str("--help=yes").unwrap_err();
+    assert_eq!(error.to_string(), "--help erwartet keinen Wert");
+
+    // The kinds without a translation keep the English text
+    let error = Sensor::parse_str("read -c").unwrap_err();
+    assert_eq!(error.to_string(), "Missing value for argument: --channel");
+}
+
+#[test]
+fn test_builder_messages() {
+    let command = Command::new(Some("sensor"), None, None, None)
+        .messages(&German)
+        .arg(ArgInfo::new("verbose").long("verbose"));
+
+    let args: vec::Vec<String> = vec!["--quiet".to_string()];
+    let error = command.parse(&args).err().unwrap();
+    assert_eq!(error.to_string(), "Unbekanntes Argument: quiet");
+    assert!(command.get_help().contains("Optionen:"));
+
+    // English stays the default
+    let error = Command::new(Some("sensor"), None, None, None).parse(&args).err().unwrap();
+    assert_eq!(error.to_string(), "Unknown argument: quiet");
+}
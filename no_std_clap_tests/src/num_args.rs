@@ -0,0 +1,83 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use no_std_clap_core::arg::arg_info::ArgInfo;
+use no_std_clap_core::command::Command;
+use no_std_clap_core::error::{ErrorKind, ParseError};
+use no_std_clap_core::parser::{parse_command_line, Parser};
+use no_std_clap_macros::Parser;
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "plot")]
+struct Plot {
+    #[arg(short, long)]
+    rgb: [u8; 3],
+
+    #[arg(short, long, num_args = 2)]
+    point: Vec<Vec<i32>>,
+
+    #[arg(short, long, num_args = 1..=3)]
+    tags: Vec<String>,
+
+    output: Option<String>,
+}
+
+#[test]
+fn test_fixed_number_of_values() {
+    let plot = Plot::parse_str("--rgb 255 128 0 --point 1 2 --point -3 4 out.png").unwrap();
+    assert_eq!(plot.rgb, [255, 128, 0]);
+    assert_eq!(plot.point, vec![vec![1, 2], vec![-3, 4]]);
+    assert_eq!(plot.tags.len(), 0);
+    assert_eq!(plot.output, Some("out.png".to_string()));
+
+    // The first value can be attached
+    let plot = Plot::parse_str("-r=1 2 3 -p1 2").unwrap();
+    assert_eq!(plot.rgb, [1, 2, 3]);
+    assert_eq!(plot.point, vec![vec![1, 2]]);
+}
+
+#[test]
+fn test_value_range() {
+    let plot = Plot::parse_str("-r 0 0 0 --tags a b --tags c d e f.png").unwrap();
+    assert_eq!(plot.tags, vec!["a", "b", "c", "d", "e"]);
+    assert_eq!(plot.output, Some("f.png".to_string()));
+
+    // Values stop at the next option
+    let plot = Plot::parse_str("--tags a -r 0 0 0").unwrap();
+    assert_eq!(plot.tags, vec!["a"]);
+}
+
+#[test]
+fn test_wrong_number_of_values() {
+    match Plot::parse_str("--rgb 1 2 --point 1 2") {
+        Err(ParseError { kind: ErrorKind::TooFewValues, context }) => {
+            assert_eq!(context.arg.as_deref(), Some("--rgb"));
+            assert_eq!((context.min_values, context.num_values), (Some(3), Some(2)));
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+    match Plot::parse_str("-r 1 2 3 out.png --point 1 2 3") {
+        Err(ParseError { kind: ErrorKind::TooManyValues, context }) => {
+            assert_eq!(context.arg.as_deref(), Some("--point"));
+            assert_eq!(context.max_values, Some(2));
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(matches!(Plot::parse_str("-r 1 2 3 --tags"), Err(ParseError { kind: ErrorKind::MissingValue, .. })));
+}
+
+#[test]
+fn test_builder_occurrences() {
+    let cmd = Command::new(Some("plot"), None, None, None)
+        .arg(ArgInfo::new("point").long("point").multiple().num_args(2..4))
+        .arg(ArgInfo::new("color").long("color").num_args(0..=1));
+
+    let args = parse_command_line("--point 1 2 3 --point 4 5 --color").unwrap();
+    let parsed = cmd.parse(&args).unwrap();
+    assert_eq!(parsed.get_occurrences("point"), vec![vec!["1", "2", "3"], vec!["4", "5"]]);
+    assert_eq!(parsed.get_all("point").len(), 5);
+
+    // An optional value
+    assert!(parsed.is_present("color"));
+    assert_eq!(parsed.get("color"), None);
+}
@@ -0,0 +1,91 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use no_std_clap_core::arg::arg_action::ArgAction;
+use no_std_clap_core::arg::arg_info::ArgInfo;
+use no_std_clap_core::command::{Command, OverridePolicy};
+use no_std_clap_core::parser::{parse_command_line, Parser};
+use no_std_clap_macros::Parser;
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "term")]
+struct Term {
+    #[arg(short, long)]
+    level: Option<u8>,
+
+    #[arg(long, negatable)]
+    color: bool,
+
+    #[arg(long, overrides_with = "verbose")]
+    quiet: bool,
+
+    #[arg(long, overrides_with = "quiet")]
+    verbose: bool,
+
+    #[arg(long)]
+    include: Vec<String>,
+}
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "term", override_policy = FirstWins)]
+struct FirstTerm {
+    #[arg(short, long)]
+    level: Option<u8>,
+}
+
+#[test]
+fn test_last_value_wins() {
+    let term = Term::parse_str("--level 1 -l 2").unwrap();
+    assert_eq!(term.level, Some(2));
+
+    // Appended values are all kept
+    let term = Term::parse_str("--include a --include b").unwrap();
+    assert_eq!(term.include, vec!["a", "b"]);
+
+    let term = FirstTerm::parse_str("--level 1 -l 2").unwrap();
+    assert_eq!(term.level, Some(1));
+}
+
+#[test]
+fn test_negatable_flag() {
+    assert!(Term::parse_str("--color").unwrap().color);
+    assert!(!Term::parse_str("--no-color").unwrap().color);
+    assert!(!Term::parse_str("--color --no-color").unwrap().color);
+    assert!(Term::parse_str("--no-color --color").unwrap().color);
+
+    assert!(Term::parse_str("--no-color=true").is_err());
+    assert!(Term::parse_str("--no-level").is_err());
+
+    let help = Term::get_help();
+    assert!(help.contains("--[no-]color"));
+}
+
+#[test]
+fn test_overrides_with() {
+    let term = Term::parse_str("--quiet --verbose").unwrap();
+    assert!(!term.quiet);
+    assert!(term.verbose);
+
+    let term = Term::parse_str("--verbose --quiet").unwrap();
+    assert!(term.quiet);
+    assert!(!term.verbose);
+}
+
+#[test]
+fn test_builder_policy() {
+    let cmd = Command::new(Some("term"), None, None, None)
+        .arg(ArgInfo::new("level").long("level"))
+        .arg(ArgInfo::new("debug").short('d').action(ArgAction::Count).overrides_with("level"));
+
+    let args = parse_command_line("--level 1 --level 2").unwrap();
+    assert_eq!(cmd.parse(&args).unwrap().get("level"), Some(&"2".to_string()));
+
+    let args = parse_command_line("--level 1 -dd").unwrap();
+    let parsed = cmd.parse(&args).unwrap();
+    assert_eq!(parsed.get("level"), None);
+    assert_eq!(parsed.count("debug"), 2);
+
+    let cmd = cmd.override_policy(OverridePolicy::FirstWins);
+    let args = parse_command_line("--level 1 --level 2").unwrap();
+    assert_eq!(cmd.parse(&args).unwrap().get("level"), Some(&"1".to_string()));
+}
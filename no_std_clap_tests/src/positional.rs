@@ -0,0 +1,115 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use no_std_clap_core::arg::arg_action::ArgAction;
+use no_std_clap_core::arg::arg_info::ArgInfo;
+use no_std_clap_core::command::Command;
+use no_std_clap_core::error::{ErrorKind, ParseError};
+use no_std_clap_core::messages::English;
+use no_std_clap_core::parser::{parse_command_line, Parser};
+use no_std_clap_core::subcommand::SubcommandInfo;
+use no_std_clap_macros::Parser;
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "runner")]
+struct Runner {
+    #[arg(short, long)]
+    verbose: bool,
+
+    program: String,
+
+    #[arg(last)]
+    args: Vec<String>,
+}
+
+#[test]
+fn test_end_of_options() {
+    let runner = Runner::parse_str("cargo -v -- --release -p core").unwrap();
+    assert!(runner.verbose);
+    assert_eq!(runner.program, "cargo");
+    assert_eq!(runner.args, vec!["--release", "-p", "core"]);
+
+    let runner = Runner::parse_str("cargo --").unwrap();
+    assert!(!runner.verbose);
+    assert!(runner.args.is_empty());
+
+    let runner = Runner::parse_str("cargo -- -v").unwrap();
+    assert!(!runner.verbose);
+    assert_eq!(runner.args, vec!["-v"]);
+}
+
+#[test]
+fn test_end_of_options_positional() {
+    let cmd = Command::new(Some("rm"), None, None, None)
+        .arg(ArgInfo::new("force").short('f').action(ArgAction::SetTrue))
+        .arg(ArgInfo::new("file"));
+
+    let args = parse_command_line("-f -- -dashed-file").unwrap();
+    let parsed = cmd.parse(&args).unwrap();
+    assert!(parsed.contains_key("force"));
+    assert_eq!(parsed.get("file"), Some(&"-dashed-file".to_string()));
+}
+
+#[test]
+fn test_last_usage() {
+    let help = SubcommandInfo::new("exec")
+        .arg(ArgInfo::new("program").required())
+        .arg(ArgInfo::new("args").last())
+        .get_help(Some("runner".to_string()), &English);
+    assert!(help.starts_with("Usage: runner exec <PROGRAM> [-- <ARGS>...]"));
+
+    let args: Vec<String> = vec!["prog".to_string(), "extra".to_string()];
+    assert!(Runner::parse_args(&args).is_err());
+}
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "copy")]
+struct Copy {
+    #[arg(short, long)]
+    recursive: bool,
+
+    #[arg(index = 3)]
+    files: Vec<String>,
+
+    #[arg(index = 1)]
+    src: String,
+
+    #[arg(index = 2)]
+    dst: Option<String>,
+}
+
+#[test]
+fn test_positional_arity() {
+    let copy = Copy::parse_str("a").unwrap();
+    assert_eq!(copy.src, "a");
+    assert_eq!(copy.dst, None);
+    assert!(copy.files.is_empty());
+
+    let copy = Copy::parse_str("a b -r c d e").unwrap();
+    assert!(copy.recursive);
+    assert_eq!(copy.src, "a");
+    assert_eq!(copy.dst, Some("b".to_string()));
+    assert_eq!(copy.files, vec!["c", "d", "e"]);
+
+}
+
+#[test]
+fn test_positional_usage() {
+    match Copy::parse_str("-r") {
+        Err(ParseError { kind: ErrorKind::MissingArguments, context }) => {
+            assert_eq!(context.expected, vec!["<SRC>"]);
+            assert_eq!(context.usage.as_deref(), Some("Usage: copy <SRC> [DST] [FILES]... [OPTIONS]"));
+        },
+        _ => panic!("Expected missing arguments"),
+    }
+
+    // Positionals without index come after the indexed ones
+    let cmd = Command::new(Some("mv"), None, None, None)
+        .arg(ArgInfo::new("files").multiple())
+        .arg(ArgInfo::new("target").index(1));
+
+    let args = parse_command_line("t x y").unwrap();
+    let parsed = cmd.parse(&args).unwrap();
+    assert_eq!(parsed.get("target"), Some(&"t".to_string()));
+    assert_eq!(parsed.get_all("files"), vec!["x", "y"]);
+}
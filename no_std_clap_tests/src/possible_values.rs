@@ -0,0 +1,55 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use no_std_clap_core::arg::arg_info::ArgInfo;
+use no_std_clap_core::command::Command;
+use no_std_clap_core::error::{ErrorKind, ParseError};
+use no_std_clap_core::parser::{parse_command_line, Parser};
+use no_std_clap_macros::Parser;
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "ls")]
+struct Ls {
+    /// When to use colors
+    #[arg(long, possible_values = ["auto", "always", "never"], default_value = "auto")]
+    color: String,
+
+    #[arg(short, long, possible_values = ["name", "size"])]
+    sort: Vec<String>,
+}
+
+#[test]
+fn test_possible_values() {
+    let ls = Ls::parse_str("--color never -s size -s name").unwrap();
+    assert_eq!(ls.color, "never");
+    assert_eq!(ls.sort, vec!["size", "name"]);
+
+    let ls = Ls::parse_str("-s name").unwrap();
+    assert_eq!(ls.color, "auto");
+
+    match Ls::parse_str("--color sometimes") {
+        Err(ParseError { kind: ErrorKind::UnknownEnumVariant, context }) => {
+            assert_eq!(context.arg.as_deref(), Some("--color"));
+            assert_eq!(context.value.as_deref(), Some("sometimes"));
+            assert_eq!(context.expected, vec!["auto", "always", "never"]);
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    assert!(matches!(Ls::parse_str("-s name -s date"), Err(ParseError { kind: ErrorKind::UnknownEnumVariant, .. })));
+}
+
+#[test]
+fn test_possible_values_help() {
+    let help = Ls::get_help();
+    assert!(help.contains("When to use colors [possible values: auto, always, never]"));
+    assert!(help.contains("[possible values: name, size]"));
+
+    let cmd = Command::new(Some("ls"), None, None, None)
+        .arg(ArgInfo::new("format").possible_values(&["long", "short"]));
+
+    let args = parse_command_line("wide").unwrap();
+    assert!(matches!(cmd.parse(&args), Err(ParseError { kind: ErrorKind::UnknownEnumVariant, .. })));
+    assert!(cmd.get_help().contains("FORMAT\t\t\t[possible values: long, short]"));
+    assert_eq!(cmd.get_args()[0].possible_values, vec!["long", "short"]);
+}
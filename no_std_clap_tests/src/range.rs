@@ -0,0 +1,124 @@
+use alloc::string::ToString;
+use no_std_clap_core::arg::arg_info::ArgInfo;
+use no_std_clap_core::command::Command;
+use no_std_clap_core::error::{ErrorKind, ParseError};
+use no_std_clap_core::parser::{parse_command_line, Parser};
+use no_std_clap_macros::Parser;
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "radio")]
+struct Radio {
+    /// Wi-Fi channel
+    #[arg(short, long, range = 1..=14)]
+    channel: u8,
+
+    #[arg(short, long, range = -1.5..=1.5)]
+    gain: Option<f32>,
+
+    #[arg(range = ..100)]
+    power: Option<i32>,
+}
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "mixer")]
+struct Mixer {
+    #[arg(long, range = 0..=10)]
+    ratio: Option<f64>,
+
+    #[arg(long, range = ..=5_000_000_000)]
+    offset: Option<u64>,
+}
+
+#[test]
+fn test_value_in_range() {
+    let radio = Radio::parse_str("-c 1 --gain -1.5 99").unwrap();
+    assert_eq!(radio.channel, 1);
+    assert_eq!(radio.gain, Some(-1.5));
+    assert_eq!(radio.power, Some(99));
+
+    let radio = Radio::parse_str("--channel 14").unwrap();
+    assert_eq!(radio.channel, 14);
+}
+
+#[test]
+fn test_value_out_of_range() {
+    match Radio::parse_str("--channel 15") {
+        Err(ParseError { kind: ErrorKind::OutOfRange, context }) => {
+            assert_eq!(context.arg.as_deref(), Some("--channel"));
+            assert_eq!(context.value.as_deref(), Some("15"));
+            assert_eq!(context.expected, ["1..=14"]);
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    assert!(matches!(Radio::parse_str("-c 0"), Err(ParseError { kind: ErrorKind::OutOfRange, .. })));
+    assert!(matches!(Radio::parse_str("-c 3 -g 1.6"), Err(ParseError { kind: ErrorKind::OutOfRange, .. })));
+    assert!(matches!(Radio::parse_str("-c 3 100"), Err(ParseError { kind: ErrorKind::OutOfRange, .. })));
+}
+
+#[test]
+fn test_range_help() {
+    let help = Radio::get_help();
+    assert!(help.contains("Wi-Fi channel [range: 1..=14]"));
+    assert!(help.contains("[range: -1.5..=1.5]"));
+    assert!(help.contains("[range: ..100]"));
+}
+
+#[test]
+fn test_builder_range() {
+    let cmd = Command::new(Some("dim"), None, None, None)
+        .arg(ArgInfo::new("level").long("level").range(0.0..1.0));
+
+    let args = parse_command_line("--level 0.5").unwrap();
+    assert!(cmd.parse(&args).is_ok());
+
+    let args = parse_command_line("--level 1").unwrap();
+    match cmd.parse(&args) {
+        Err(error) => assert_eq!(error.to_string(), "Invalid value: 1 for --level, expected a value in 0..1"),
+        Ok(_) => panic!("Expected an out of range value"),
+    }
+}
+
+#[test]
+fn test_integer_range_precision() {
+    // 2^53 + 1 is rounded down to 2^53 as f64, integer bounds compare it exactly
+    let cmd = Command::new(Some("seek"), None, None, None)
+        .arg(ArgInfo::new("offset").long("offset").range(..=9_007_199_254_740_992u64))
+        .arg(ArgInfo::new("level").long("level").range(0.0..1.0));
+
+    let args = parse_command_line("--offset 9007199254740992").unwrap();
+    assert!(cmd.parse(&args).is_ok());
+
+    let args = parse_command_line("--offset 9007199254740993").unwrap();
+    assert!(matches!(cmd.parse(&args), Err(ParseError { kind: ErrorKind::OutOfRange, .. })));
+
+    // A float value is compared to integer bounds as a float
+    let args = parse_command_line("--offset 1e20").unwrap();
+    assert!(matches!(cmd.parse(&args), Err(ParseError { kind: ErrorKind::OutOfRange, .. })));
+
+    // Values which are not numbers are reported instead of skipped
+
+    let args = parse_command_line("--level high").unwrap();
+    match cmd.parse(&args) {
+        Err(error) => assert_eq!(error.to_string(), "Invalid value: high for --level, expected a value in 0..1"),
+        Ok(_) => panic!("Expected an out of range value"),
+    }
+}
+
+#[test]
+fn test_range_of_field_type() {
+    // The bounds take the type of the field: integers bound a float field, and fit a large `u64`
+    let mixer = Mixer::parse_str("--ratio 2.5 --offset 5000000000").unwrap();
+    assert_eq!(mixer.ratio, Some(2.5));
+    assert_eq!(mixer.offset, Some(5_000_000_000));
+
+    assert!(matches!(Mixer::parse_str("--ratio 10.5"), Err(ParseError { kind: ErrorKind::OutOfRange, .. })));
+    assert!(matches!(Mixer::parse_str("--offset 5000000001"), Err(ParseError { kind: ErrorKind::OutOfRange, .. })));
+
+    // A float value is compared to the integer bounds of a builder range
+    let cmd = Command::new(Some("mixer"), None, None, None)
+        .arg(ArgInfo::new("ratio").long("ratio").range(0..=10));
+
+    let args = parse_command_line("--ratio 2.5").unwrap();
+    assert!(cmd.parse(&args).is_ok());
+}
@@ -0,0 +1,68 @@
+use alloc::string::String;
+use no_std_clap_core::arg::arg_action::ArgAction;
+use no_std_clap_core::arg::arg_info::ArgInfo;
+use no_std_clap_core::command::Command;
+use no_std_clap_core::error::{ErrorKind, ParseError};
+use no_std_clap_core::parser::{parse_command_line, Parser};
+use no_std_clap_macros::Parser;
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "flash")]
+struct Flash {
+    #[arg(short, long, conflicts_with = "dry_run")]
+    force: bool,
+
+    #[arg(long)]
+    dry_run: bool,
+
+    #[arg(long, requires = "cert")]
+    key: Option<String>,
+
+    #[arg(long)]
+    cert: Option<String>,
+}
+
+#[test]
+fn test_conflicts_with() {
+    assert!(Flash::parse_str("--force").is_ok());
+    assert!(Flash::parse_str("--dry-run").is_ok());
+
+    match Flash::parse_str("--dry-run -f") {
+        Err(ParseError { kind: ErrorKind::ArgumentConflict, context }) => {
+            assert_eq!(context.arg.as_deref(), Some("--force"));
+            assert_eq!(context.expected, ["--dry-run"]);
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_requires() {
+    let flash = Flash::parse_str("--key k.pem --cert c.pem").unwrap();
+    assert_eq!(flash.key.as_deref(), Some("k.pem"));
+
+    // Only the argument declaring the requirement needs the other one
+    assert!(Flash::parse_str("--cert c.pem").is_ok());
+
+    match Flash::parse_str("--key k.pem") {
+        Err(ParseError { kind: ErrorKind::MissingRequirement, context }) => {
+            assert_eq!(context.arg.as_deref(), Some("--key"));
+            assert_eq!(context.expected, ["--cert"]);
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_builder_relations() {
+    let cmd = Command::new(Some("net"), None, None, None)
+        .arg(ArgInfo::new("ipv4").short('4').action(ArgAction::SetTrue).conflicts_with("ipv6"))
+        .arg(ArgInfo::new("ipv6").short('6').action(ArgAction::SetTrue).conflicts_with("ipv4"))
+        .arg(ArgInfo::new("host"));
+
+    let args = parse_command_line("-4 example.com").unwrap();
+    assert!(cmd.parse(&args).is_ok());
+
+    let args = parse_command_line("-46 example.com").unwrap();
+    assert!(matches!(cmd.parse(&args), Err(ParseError { kind: ErrorKind::ArgumentConflict, .. })));
+}
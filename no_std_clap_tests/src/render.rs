@@ -0,0 +1,66 @@
+use alloc::string::ToString;
+use no_std_clap_core::error::ErrorKind;
+use no_std_clap_core::parser::Parser;
+use no_std_clap_macros::{Args, Parser, Subcommand};
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "flash", version = "1.2.0")]
+struct Flash {
+    #[arg(short, long)]
+    verbose: bool,
+
+    #[command(subcommand)]
+    command: Option<FlashCommand>,
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+enum FlashCommand {
+    Write(WriteArgs),
+}
+
+#[derive(Args, Debug, PartialEq)]
+struct WriteArgs {
+    #[arg(short, long)]
+    address: u32,
+
+    image: alloc::string::String,
+}
+
+#[test]
+fn test_render() {
+    let error = Flash::parse_str("--verbos").unwrap_err();
+    let usage = error.context.usage.clone().unwrap();
+    assert!(usage.starts_with("Usage: flash"));
+    assert_eq!(error.render(), alloc::format!("error: Unknown argument: verbos, did you mean '--verbose'?\n\n{}\n\nFor more information, try '--help'", usage));
+    assert_eq!(error.exit_code(), 2);
+}
+
+#[test]
+fn test_render_subcommand_usage() {
+    // Errors of the parse and of the field conversions both get the usage of their subcommand
+    let error = Flash::parse_str("write boot.bin --size 4").unwrap_err();
+    assert_eq!(error.context.subcommand.as_deref(), Some("write"));
+    assert!(error.context.usage.as_deref().unwrap().starts_with("Usage: flash write"));
+
+    let error = Flash::parse_str("write boot.bin -a high").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::InvalidValue);
+    assert_eq!(error.context.subcommand.as_deref(), Some("write"));
+    assert!(error.render().starts_with("error: Invalid value: Cannot parse 'high' as u32\n\nUsage: flash write"));
+
+    // The usage is not repeated for missing arguments
+    let error = Flash::parse_str("write boot.bin").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::MissingArguments);
+    assert_eq!(error.render().matches("Usage:").count(), 1);
+}
+
+#[test]
+fn test_exit_code() {
+    let error = Flash::parse_str("--help").unwrap_err();
+    assert_eq!(error.exit_code(), 0);
+    assert_eq!(error.render(), error.to_string());
+
+    let error = Flash::parse_str("-V").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Version);
+    assert_eq!(error.exit_code(), 0);
+    assert_eq!(error.render(), "flash 1.2.0");
+}
@@ -0,0 +1,89 @@
+use alloc::string::{String, ToString};
+use no_std_clap_core::error::ErrorKind;
+use no_std_clap_core::parser::{parse_command_line_spans, Parser, Token};
+use no_std_clap_macros::{Args, Parser, Subcommand};
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "uart")]
+struct Uart {
+    #[arg(short, long)]
+    verbose: bool,
+
+    #[arg(short, long)]
+    baud: Option<u32>,
+
+    #[command(subcommand)]
+    command: Option<UartCommand>,
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+enum UartCommand {
+    Send(SendArgs),
+}
+
+#[derive(Args, Debug, PartialEq)]
+struct SendArgs {
+    message: String,
+}
+
+#[test]
+fn test_token_spans() {
+    let tokens = parse_command_line_spans("  send  \"hello world\" -v\r\n").unwrap();
+    assert_eq!(tokens, [
+        Token { text: String::from("send"), span: 2..6 },
+        Token { text: String::from("hello world"), span: 8..21 },
+        Token { text: String::from("-v"), span: 22..24 },
+    ]);
+
+    let error = parse_command_line_spans("send 'hello").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::UnclosedQuote);
+    assert_eq!(error.context.span, Some(5..11));
+}
+
+#[test]
+fn test_error_span() {
+    let error = Uart::parse_str("-v --verbos").unwrap_err();
+    assert_eq!(error.context.span, Some(3..11));
+
+    let error = Uart::parse_str("-v --baud fast").unwrap_err();
+    assert_eq!(error.context.span, Some(10..14));
+
+    // Only the attached value is underlined
+    let error = Uart::parse_str("--baud=fast").unwrap_err();
+    assert_eq!(error.context.span, Some(7..11));
+
+    let error = Uart::parse_str("-vx").unwrap_err();
+    assert_eq!(error.context.span, Some(0..3));
+
+    // The repeated value is underlined, not the first one with the same text
+    let error = Uart::parse_str("--baud 5 5").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::UnknownArgument);
+    assert_eq!(error.context.span, Some(9..10));
+
+    let error = Uart::parse_str("send hi hi").unwrap_err();
+    assert_eq!(error.context.token_index, Some(2));
+    assert_eq!(error.context.span, Some(8..10));
+
+    // Nothing to point at
+    let error = Uart::parse_str("send").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::MissingArguments);
+    assert_eq!(error.context.span, None);
+
+    // Only `parse_str` knows the line
+    let error = Uart::parse_from(["--verbos"]).unwrap_err();
+    assert_eq!(error.context.span, None);
+}
+
+#[test]
+fn test_render_source() {
+    let input = "-b 9600 --verbos";
+    let error = Uart::parse_str(input).unwrap_err();
+    assert_eq!(error.render_source(input), "Unknown argument: verbos, did you mean '--verbose'?\n  -b 9600 --verbos\n          ^^^^^^^^");
+
+    let input = "send 'hi";
+    let error = Uart::parse_str(input).unwrap_err();
+    assert_eq!(error.render_source(input), "Unclosed quote in command line\n  send 'hi\n       ^^^");
+
+    let error = Uart::parse_str("send").unwrap_err();
+    assert_eq!(error.render_source("send"), error.to_string());
+}
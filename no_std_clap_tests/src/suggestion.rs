@@ -0,0 +1,80 @@
+use alloc::string::{String, ToString};
+use no_std_clap_core::error::ErrorKind;
+use no_std_clap_core::parser::Parser;
+use no_std_clap_core::suggestion::{did_you_mean, levenshtein};
+use no_std_clap_macros::{Args, EnumValuesArg, Parser, Subcommand};
+
+#[derive(EnumValuesArg, Debug, PartialEq, Clone)]
+enum Format {
+    Json,
+    ComposedName,
+}
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "pkg")]
+struct Pkg {
+    #[arg(short, long)]
+    verbose: bool,
+
+    #[arg(long)]
+    format: Option<Format>,
+
+    #[arg(long, possible_values = ["auto", "always", "never"])]
+    color: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<PkgCommand>,
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+enum PkgCommand {
+    Install(NameArgs),
+    Remove(NameArgs),
+}
+
+#[derive(Args, Debug, PartialEq)]
+struct NameArgs {
+    name: String,
+}
+
+#[test]
+fn test_levenshtein() {
+    assert_eq!(levenshtein("verbose", "verbose"), 0);
+    assert_eq!(levenshtein("verbos", "verbose"), 1);
+    assert_eq!(levenshtein("remvoe", "remove"), 2);
+    assert_eq!(levenshtein("", "abc"), 3);
+    assert_eq!(did_you_mean("instal", ["install", "remove"]), Some(String::from("install")));
+    assert_eq!(did_you_mean("xyz", ["install", "remove"]), None);
+}
+
+#[test]
+fn test_suggest_long() {
+    let error = Pkg::parse_str("--verbos").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::UnknownArgument);
+    assert_eq!(error.context.suggestion.as_deref(), Some("--verbose"));
+    assert_eq!(error.to_string(), "Unknown argument: verbos, did you mean '--verbose'?");
+
+    let error = Pkg::parse_str("--output").unwrap_err();
+    assert_eq!(error.context.suggestion, None);
+    assert_eq!(error.to_string(), "Unknown argument: output");
+}
+
+#[test]
+fn test_suggest_subcommand() {
+    let error = Pkg::parse_str("remvoe foo").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::UnknownArgument);
+    assert_eq!(error.context.suggestion.as_deref(), Some("remove"));
+    assert_eq!(error.to_string(), "Unknown argument: remvoe, did you mean 'remove'?");
+}
+
+#[test]
+fn test_suggest_value() {
+    let error = Pkg::parse_str("--format composd-name").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::UnknownEnumVariant);
+    assert_eq!(error.context.suggestion.as_deref(), Some("composed-name"));
+
+    let error = Pkg::parse_str("--color alway").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::UnknownEnumVariant);
+    assert_eq!(error.context.suggestion.as_deref(), Some("always"));
+    assert_eq!(error.to_string(), "Invalid value: alway, possible values are: auto|always|never, did you mean 'always'?");
+}
@@ -0,0 +1,139 @@
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+use core::num::ParseIntError;
+use no_std_clap_core::arg::arg_info::ArgInfo;
+use no_std_clap_core::arg::from_arg::{FromArg, TryFromArg};
+use no_std_clap_core::error::ErrorKind;
+use no_std_clap_core::parser::Parser;
+use no_std_clap_macros::{Args, Parser, Subcommand};
+
+// Pin of a GPIO port, e.g. `PA5`
+#[derive(Debug, PartialEq)]
+struct Pin {
+    port: char,
+    number: u8,
+}
+
+#[derive(Debug, PartialEq)]
+enum PinError {
+    UnknownPort(char),
+    // Argument name, pin number
+    NoSuchPin(alloc::string::String, u8),
+    Number(ParseIntError),
+}
+
+impl fmt::Display for PinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PinError::UnknownPort(port) => write!(f, "no port {}", port),
+            PinError::NoSuchPin(arg, number) => write!(f, "{} has no pin {}", arg, number),
+            PinError::Number(error) => write!(f, "bad pin number: {}", error),
+        }
+    }
+}
+
+impl Error for PinError {}
+
+impl TryFromArg for Pin {
+    type Error = PinError;
+
+    fn try_from_arg(arg: &str, info: Option<&ArgInfo>) -> Result<Self, Self::Error> {
+        let rest = arg.strip_prefix('P').unwrap_or(arg);
+        let mut chars = rest.chars();
+        let port = chars.next().unwrap_or('?');
+
+        if port != 'A' && port != 'B' {
+            return Err(PinError::UnknownPort(port));
+        }
+
+        let number = chars.as_str().parse().map_err(PinError::Number)?;
+
+        if number > 15 {
+            return Err(PinError::NoSuchPin(info.map(|info| info.name.clone()).unwrap_or_default(), number));
+        }
+
+        Ok(Pin { port, number })
+    }
+}
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "gpio")]
+struct Gpio {
+    #[arg(short, long)]
+    led: Pin,
+
+    #[arg(short, long)]
+    button: Option<Pin>,
+
+    #[arg(short, long)]
+    extra: Vec<Pin>,
+
+    #[arg(short, long)]
+    count: Option<u8>,
+
+    #[command(subcommand)]
+    command: Option<GpioCommand>,
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+enum GpioCommand {
+    Toggle {
+        #[arg(short, long)]
+        pin: Pin,
+    },
+    Read(ReadArgs),
+}
+
+#[derive(Args, Debug, PartialEq)]
+struct ReadArgs {
+    #[arg(short, long)]
+    pin: Pin,
+}
+
+#[test]
+fn test_try_from_arg() {
+    let gpio = Gpio::parse_str("--led PA5 -e PB1 -e PB2").unwrap();
+    assert_eq!(gpio.led, Pin { port: 'A', number: 5 });
+    assert_eq!(gpio.button, None);
+    assert_eq!(gpio.extra, [Pin { port: 'B', number: 1 }, Pin { port: 'B', number: 2 }]);
+
+    // Still a `FromArg` outside of a command
+    assert_eq!(Pin::from_arg("PA3").unwrap(), Pin { port: 'A', number: 3 });
+
+    // Without a command the conversion is given no argument
+    let error = Pin::from_arg("PA16").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::InvalidValue);
+    assert_eq!(error.context.arg, None);
+    assert_eq!(error.context.message.as_deref(), Some(" has no pin 16"));
+}
+
+#[test]
+fn test_try_from_arg_context() {
+    let error = Gpio::parse_str("--led PA5 --button PA16").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::InvalidArgumentValue);
+    assert_eq!(error.context.arg.as_deref(), Some("--button"));
+    assert_eq!(error.context.value.as_deref(), Some("PA16"));
+    assert_eq!(error.to_string(), "Invalid value for --button: button has no pin 16");
+
+    // The subcommands give the arguments of their level
+    let error = Gpio::parse_str("--led PA5 toggle --pin PB16").unwrap_err();
+    assert_eq!(error.to_string(), "Invalid value for --pin: pin has no pin 16");
+
+    let error = Gpio::parse_str("--led PA5 read -p PA20").unwrap_err();
+    assert_eq!(error.to_string(), "Invalid value for --pin: pin has no pin 20");
+}
+
+#[test]
+fn test_error_source() {
+    let error = Gpio::parse_str("--led PAx").unwrap_err();
+    let source = error.context.source.as_deref().unwrap().downcast_ref::<PinError>().unwrap();
+    assert!(matches!(source, PinError::Number(_)));
+    assert_eq!(error.source().unwrap().to_string(), "bad pin number: invalid digit found in string");
+
+    // The built-in conversions keep their error as well
+    let error = Gpio::parse_str("--led PA5 --count 300").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::InvalidValue);
+    assert!(error.context.source.as_deref().unwrap().downcast_ref::<ParseIntError>().is_some());
+}
@@ -0,0 +1,138 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use no_std_clap_core::arg::arg_action::ArgAction;
+use no_std_clap_core::arg::arg_info::ArgInfo;
+use no_std_clap_core::command::Command;
+use no_std_clap_core::error::{ErrorKind, ParseError};
+use no_std_clap_core::parser::{parse_command_line, Parser};
+use no_std_clap_core::subcommand::SubcommandInfo;
+use no_std_clap_macros::{Args, Parser, Subcommand};
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "deploy")]
+struct Deploy {
+    #[arg(short, long)]
+    target: String,
+
+    #[arg(short, long, required)]
+    port: Option<u16>,
+
+    #[command(subcommand)]
+    command: Option<DeployCommand>,
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+enum DeployCommand {
+    Upload(UploadArgs),
+    Tag {
+        name: String,
+    },
+}
+
+#[derive(Args, Debug, PartialEq)]
+struct UploadArgs {
+    file: String,
+
+    #[arg(short, long)]
+    force: bool,
+}
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "rename")]
+struct Rename {
+    #[arg(action = Set)]
+    file: String,
+
+    #[arg(long, action = Set)]
+    name: String,
+
+    #[arg(long)]
+    other: Option<String>,
+}
+
+#[test]
+fn test_required_explicit_action() {
+    // An explicit value taking action keeps the field required
+    match Rename::parse_str("--other x") {
+        Err(ParseError { kind: ErrorKind::MissingArguments, context }) => {
+            assert_eq!(context.expected, vec!["<FILE>", "--name"]);
+            assert_eq!(context.usage.as_deref(), Some("Usage: rename <FILE> [OPTIONS]"));
+        },
+        _ => panic!("Expected missing arguments"),
+    }
+
+    let rename = Rename::parse_str("a.txt --name b.txt").unwrap();
+    assert_eq!(rename.name, "b.txt");
+}
+
+#[test]
+fn test_required_all_reported() {
+    match Deploy::parse_str("") {
+        Err(ParseError { kind: ErrorKind::EmptyInput, .. }) => {},
+        _ => panic!("Expected empty input"),
+    }
+
+    match Deploy::parse_str("-f") {
+        Err(ParseError { kind: ErrorKind::UnknownArgument, .. }) => {},
+        _ => panic!("Expected unknown argument"),
+    }
+
+    let args = vec![String::from("upload"), String::from("--force")];
+    match Deploy::parse_args(&args) {
+        Err(ParseError { kind: ErrorKind::MissingArguments, context }) => {
+            assert_eq!(context.expected, vec!["--target", "--port", "<FILE>"]);
+            assert_eq!(context.usage.as_deref(), Some("Usage: deploy upload <FILE> [OPTIONS]"));
+            assert_eq!(context.subcommand.as_deref(), Some("upload"));
+        },
+        _ => panic!("Expected missing arguments"),
+    }
+
+    let deploy = Deploy::parse_str("-t board -p 22 upload firmware.bin").unwrap();
+    assert_eq!(deploy.target, "board");
+    assert_eq!(deploy.port, Some(22));
+    assert_eq!(deploy.command, Some(DeployCommand::Upload(UploadArgs { file: "firmware.bin".to_string(), force: false })));
+}
+
+#[test]
+fn test_required_skipped_for_help() {
+    assert!(matches!(Deploy::parse_str("--help"), Err(ParseError { kind: ErrorKind::Help, .. })));
+    assert!(matches!(Deploy::parse_str("upload --help"), Err(ParseError { kind: ErrorKind::Help, .. })));
+
+    // A variant with named fields as well
+    match Deploy::parse_str("-t board -p 22 tag --help") {
+        Err(ParseError { kind: ErrorKind::Help, context }) => assert!(context.message.unwrap().contains("Usage: tag <NAME>")),
+        _ => panic!("Expected help"),
+    }
+
+    let deploy = Deploy::parse_str("-t board -p 22 tag v1").unwrap();
+    assert_eq!(deploy.command, Some(DeployCommand::Tag { name: "v1".to_string() }));
+}
+
+#[test]
+fn test_required_builder() {
+    let cmd = Command::new(Some("serial"), None, None, None)
+        .arg(ArgInfo::new("baud").long("baud").required())
+        .arg(ArgInfo::new("verbose").short('v').action(ArgAction::Count).required().global())
+        .subcommand(
+            SubcommandInfo::new("open")
+                .arg(ArgInfo::new("device").required())
+        );
+
+    let args = parse_command_line("open").unwrap();
+    match cmd.parse(&args) {
+        Err(ParseError { kind: ErrorKind::MissingArguments, context }) => {
+            assert_eq!(context.expected, vec!["--baud", "<DEVICE>", "-v"]);
+            assert_eq!(context.usage.as_deref(), Some("Usage: serial open <DEVICE> [OPTIONS]"));
+        },
+        _ => panic!("Expected missing arguments"),
+    }
+
+    // The global arguments are options of every level
+    assert_eq!(cmd.get_usage(Some("open")), "Usage: serial open <DEVICE> [OPTIONS]");
+
+    // Global arguments can be given after the subcommand
+    let args = parse_command_line("--baud 9600 open /dev/ttyUSB0 -v").unwrap();
+    let parsed = cmd.parse(&args).unwrap();
+    assert_eq!(parsed.get("baud"), Some(&"9600".to_string()));
+    assert_eq!(parsed.count("verbose"), 1);
+}
@@ -0,0 +1,66 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::num::ParseIntError;
+use no_std_clap_core::error::{ErrorKind, ParseError};
+use no_std_clap_core::parser::Parser;
+use no_std_clap_macros::Parser;
+
+fn parse_hex(value: &str) -> Result<u32, ParseIntError> {
+    u32::from_str_radix(value.trim_start_matches("0x"), 16)
+}
+
+fn parse_key_value(value: &str) -> Result<(String, String), &'static str> {
+    value.split_once('=')
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .ok_or("expected KEY=VALUE")
+}
+
+mod parsers {
+    pub fn parse_percent(value: &str) -> Result<f32, &'static str> {
+        let number = value.strip_suffix('%').ok_or("missing %")?;
+        number.parse::<f32>().map(|n| n / 100.0).map_err(|_| "not a number")
+    }
+}
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "poke")]
+struct Poke {
+    #[arg(short, long, value_parser = parse_hex)]
+    address: u32,
+
+    #[arg(short, long, value_parser = parse_key_value)]
+    define: Vec<(String, String)>,
+
+    #[arg(long, value_parser = parsers::parse_percent)]
+    duty: Option<f32>,
+}
+
+#[test]
+fn test_value_parser() {
+    let poke = Poke::parse_str("-a 0x1F -d a=1 --define b=2 --duty 50%").unwrap();
+    assert_eq!(poke.address, 0x1F);
+    assert_eq!(poke.define, vec![("a".to_string(), "1".to_string()), ("b".to_string(), "2".to_string())]);
+    assert_eq!(poke.duty, Some(0.5));
+}
+
+#[test]
+fn test_value_parser_error() {
+    match Poke::parse_str("--address zz") {
+        Err(ParseError { kind: ErrorKind::InvalidArgumentValue, context }) => {
+            assert_eq!(context.arg.as_deref(), Some("--address"));
+            assert_eq!(context.value.as_deref(), Some("zz"));
+            assert_eq!(context.message.as_deref(), Some("invalid digit found in string"));
+            assert!(context.source.unwrap().downcast_ref::<ParseIntError>().is_some());
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    match Poke::parse_str("-a 1 -d nothing") {
+        Err(error) => {
+            assert_eq!(error.to_string(), "Invalid value for --define: expected KEY=VALUE");
+            assert_eq!(error.context.source.unwrap().to_string(), "expected KEY=VALUE");
+        },
+        Ok(_) => panic!("Expected an invalid value"),
+    }
+}
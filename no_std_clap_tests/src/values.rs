@@ -0,0 +1,86 @@
+use alloc::string::{String, ToString};
+use no_std_clap_core::arg::arg_action::ArgAction;
+use no_std_clap_core::arg::arg_info::ArgInfo;
+use no_std_clap_core::command::Command;
+use no_std_clap_core::error::{ErrorKind, ParseError};
+use no_std_clap_core::parser::{parse_command_line, Parser};
+use no_std_clap_macros::Parser;
+
+#[derive(Parser, Debug, PartialEq)]
+#[clap(name = "thermo")]
+struct Thermo {
+    #[arg(short, long)]
+    offset: Option<i32>,
+
+    #[arg(short, long)]
+    temp: Option<f32>,
+
+    #[arg(short, long, allow_hyphen_values)]
+    pattern: Option<String>,
+
+    #[arg(short, long)]
+    verbose: bool,
+}
+
+#[test]
+fn test_negative_number_values() {
+    let thermo = Thermo::parse_str("--offset -5 --temp -12.5 -v").unwrap();
+    assert_eq!(thermo.offset, Some(-5));
+    assert_eq!(thermo.temp, Some(-12.5));
+    assert!(thermo.verbose);
+
+    let thermo = Thermo::parse_str("-o -5 -t -.5").unwrap();
+    assert_eq!(thermo.offset, Some(-5));
+    assert_eq!(thermo.temp, Some(-0.5));
+}
+
+#[test]
+fn test_allow_hyphen_values() {
+    let thermo = Thermo::parse_str("--pattern -foo --verbose").unwrap();
+    assert_eq!(thermo.pattern, Some("-foo".to_string()));
+    assert!(thermo.verbose);
+
+    let thermo = Thermo::parse_str("-p --verbose").unwrap();
+    assert_eq!(thermo.pattern, Some("--verbose".to_string()));
+    assert!(!thermo.verbose);
+
+    // Without allow_hyphen_values, `-foo` is parsed as a cluster of shorts
+    assert!(Thermo::parse_str("--temp -foo").is_err());
+}
+
+#[test]
+fn test_negative_number_with_digit_short() {
+    let cmd = Command::new(Some("calc"), None, None, None)
+        .arg(ArgInfo::new("value").long("value"))
+        .arg(ArgInfo::new("five").short('5').action(ArgAction::SetTrue))
+        .arg(ArgInfo::new("operand"));
+
+    // A `-5` short exists, so `-5` is not a value anymore
+    let args = parse_command_line("--value -5").unwrap();
+    assert!(matches!(cmd.parse(&args), Err(ParseError { kind: ErrorKind::MissingValue, .. })));
+
+    let args = parse_command_line("--value=-5 -5").unwrap();
+    let parsed = cmd.parse(&args).unwrap();
+    assert_eq!(parsed.get_flag("five"), Some(true));
+    assert_eq!(parsed.get("value"), Some(&"-5".to_string()));
+
+    let cmd = Command::new(Some("calc"), None, None, None)
+        .arg(ArgInfo::new("operand"));
+
+    let args = parse_command_line("-3").unwrap();
+    let parsed = cmd.parse(&args).unwrap();
+    assert_eq!(parsed.get("operand"), Some(&"-3".to_string()));
+}
+
+#[test]
+fn test_lone_hyphen_value() {
+    // `-` usually stands for stdin, it is a value of options as well as of positionals
+    let cmd = Command::new(Some("cat"), None, None, None)
+        .arg(ArgInfo::new("file").long("file"))
+        .arg(ArgInfo::new("output"));
+
+    let args = parse_command_line("--file - -").unwrap();
+    let parsed = cmd.parse(&args).unwrap();
+    assert_eq!(parsed.get("file"), Some(&"-".to_string()));
+    assert_eq!(parsed.get("output"), Some(&"-".to_string()));
+}
@@ -0,0 +1,12 @@
+extern crate alloc;
+
+use no_std_clap_macros::EnumValuesArg;
+
+#[derive(EnumValuesArg, Debug, Clone, Copy)]
+enum Mode {
+    FastBuild,
+    #[arg(name = "fast-build")]
+    Quick,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+extern crate alloc;
+
+use no_std_clap_macros::EnumValuesArg;
+
+#[derive(EnumValuesArg, Debug, Clone, Copy)]
+enum Protocol {
+    HttpServer,
+    HTTPServer,
+}
+
+fn main() {}
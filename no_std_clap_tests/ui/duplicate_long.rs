@@ -0,0 +1,14 @@
+extern crate alloc;
+
+use no_std_clap_macros::Args;
+
+#[derive(Args)]
+struct BuildArgs {
+    #[arg(long)]
+    target: Option<String>,
+
+    #[arg(long = "target")]
+    release: bool,
+}
+
+fn main() {}
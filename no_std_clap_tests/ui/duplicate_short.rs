@@ -0,0 +1,15 @@
+extern crate alloc;
+
+use no_std_clap_macros::Parser;
+
+#[derive(Parser)]
+#[clap(name = "tool")]
+struct Cli {
+    #[arg(short)]
+    name: Option<String>,
+
+    #[arg(short)]
+    number: Option<u32>,
+}
+
+fn main() {}
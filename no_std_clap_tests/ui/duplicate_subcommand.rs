@@ -0,0 +1,12 @@
+extern crate alloc;
+
+use no_std_clap_macros::Subcommand;
+
+#[derive(Subcommand)]
+enum Commands {
+    Push,
+    #[command(name = "push")]
+    Upload,
+}
+
+fn main() {}
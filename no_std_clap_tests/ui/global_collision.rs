@@ -0,0 +1,26 @@
+extern crate alloc;
+
+use no_std_clap_macros::{Args, Parser, Subcommand};
+
+#[derive(Parser)]
+#[clap(name = "tool")]
+struct Cli {
+    #[arg(short, long, global)]
+    verbose: bool,
+
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    Push(PushArgs),
+}
+
+#[derive(Args)]
+struct PushArgs {
+    #[arg(short)]
+    value: Option<String>,
+}
+
+fn main() {}
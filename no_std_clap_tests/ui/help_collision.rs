@@ -0,0 +1,12 @@
+extern crate alloc;
+
+use no_std_clap_macros::Parser;
+
+#[derive(Parser)]
+#[clap(name = "tool")]
+struct Cli {
+    #[arg(short)]
+    host: Option<String>,
+}
+
+fn main() {}
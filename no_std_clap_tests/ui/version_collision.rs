@@ -0,0 +1,12 @@
+extern crate alloc;
+
+use no_std_clap_macros::Parser;
+
+#[derive(Parser)]
+#[clap(name = "tool", version = "1.0.0")]
+struct Cli {
+    #[arg(long)]
+    version: Option<String>,
+}
+
+fn main() {}